@@ -0,0 +1,35 @@
+//! Interactive shell for a DS1307 on Linux hosts.
+//!
+//! Usage: linux_console [I2C device]
+//!
+//! Reads commands from the standard input, one per line. Type `help` for a
+//! list of commands.
+
+use ds1307::{Console, Ds1307};
+use linux_embedded_hal::I2cdev;
+use std::env;
+use std::io::{self, BufRead, Write};
+
+fn main() {
+    let device = env::args().nth(1).unwrap_or_else(|| "/dev/i2c-1".into());
+    let dev = I2cdev::new(device).unwrap();
+    let mut rtc = Ds1307::new(dev);
+    let console = Console::new();
+    let stdin = io::stdin();
+    let mut out = String::new();
+    print!("rtc> ");
+    io::stdout().flush().unwrap();
+    for line in stdin.lock().lines() {
+        let line = line.unwrap();
+        if line.trim() == "exit" {
+            break;
+        }
+        out.clear();
+        match console.execute(&mut rtc, &line, &mut out) {
+            Ok(()) => print!("{out}"),
+            Err(e) => println!("error: {e:?}"),
+        }
+        print!("rtc> ");
+        io::stdout().flush().unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+//! Daemon synchronizing the system clock with a DS1307 on Linux hosts
+//! without a kernel RTC driver for it.
+//!
+//! Usage: linux_sync_daemon [I2C device] [sync interval in seconds] [startup policy]
+//!
+//! The startup policy can be `never`, `always`, `behind` or a number of
+//! seconds of drift above which the system time is set.
+//! Setting the system time requires the `CAP_SYS_TIME` capability.
+
+use chrono::DateTime;
+use ds1307::{ClockSync, Ds1307, NaiveDateTime, StartupPolicy, SystemClock};
+use linux_embedded_hal::I2cdev;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, io, thread};
+
+struct LinuxSystemClock;
+
+impl SystemClock for LinuxSystemClock {
+    type Error = io::Error;
+
+    fn now(&mut self) -> Result<NaiveDateTime, Self::Error> {
+        let elapsed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        DateTime::from_timestamp(elapsed.as_secs() as i64, elapsed.subsec_nanos())
+            .map(|datetime| datetime.naive_utc())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "invalid system time"))
+    }
+
+    fn set(&mut self, datetime: &NaiveDateTime) -> Result<(), Self::Error> {
+        let time = libc::timespec {
+            tv_sec: datetime.and_utc().timestamp() as libc::time_t,
+            tv_nsec: 0,
+        };
+        // SAFETY: `time` is a valid timespec for the duration of the call.
+        if unsafe { libc::clock_settime(libc::CLOCK_REALTIME, &time) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+fn parse_policy(policy: &str) -> StartupPolicy {
+    match policy {
+        "never" => StartupPolicy::Never,
+        "always" => StartupPolicy::Always,
+        "behind" => StartupPolicy::IfSystemBehind,
+        seconds => StartupPolicy::IfDriftExceeds(seconds.parse().expect("invalid policy")),
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let device = args.next().unwrap_or_else(|| "/dev/i2c-1".into());
+    let interval = args
+        .next()
+        .map_or(660, |s| s.parse().expect("invalid interval"));
+    let policy = args
+        .next()
+        .map_or(StartupPolicy::Always, |s| parse_policy(&s));
+
+    let mut rtc = Ds1307::new(I2cdev::new(device).unwrap());
+    let mut clock = LinuxSystemClock;
+    let sync = ClockSync::new(policy);
+
+    match sync.rtc_to_system(&mut rtc, &mut clock) {
+        Ok(report) => println!(
+            "RTC: {}, system: {}, drift: {} s, system time set: {}",
+            report.rtc,
+            report.system,
+            report.drift().num_seconds(),
+            report.system_updated
+        ),
+        Err(e) => eprintln!("Error setting the system time: {e:?}"),
+    }
+
+    loop {
+        thread::sleep(Duration::from_secs(interval));
+        match sync.system_to_rtc(&mut rtc, &mut clock) {
+            Ok(report) => println!("RTC drift: {} s", report.drift().num_seconds()),
+            Err(e) => eprintln!("Error writing the RTC: {e:?}"),
+        }
+    }
+}
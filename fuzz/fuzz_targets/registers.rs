@@ -0,0 +1,103 @@
+//! Feeds arbitrary register images into every getter.
+//!
+//! The first 64 bytes of the input are the device registers (missing bytes
+//! are zero). The next two bytes, if present, select the user RAM region
+//! used for the RAM data structures.
+
+#![no_main]
+
+use ds1307::{DateTimeAccess, Ds1307, EventLog, KvStore, Rtcc};
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
+use libfuzzer_sys::fuzz_target;
+
+/// Simulated device backed by a 64-byte register image.
+struct RegisterImage {
+    registers: [u8; 64],
+    pointer: usize,
+}
+
+impl ErrorType for RegisterImage {
+    type Error = core::convert::Infallible;
+}
+
+impl I2c for RegisterImage {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => {
+                    if let Some((register, payload)) = data.split_first() {
+                        self.pointer = usize::from(*register) % self.registers.len();
+                        for byte in payload {
+                            self.registers[self.pointer] = *byte;
+                            self.pointer = (self.pointer + 1) % self.registers.len();
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.registers[self.pointer];
+                        self.pointer = (self.pointer + 1) % self.registers.len();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut registers = [0; 64];
+    let len = data.len().min(registers.len());
+    registers[..len].copy_from_slice(&data[..len]);
+    let offset = data.get(64).copied().unwrap_or(0);
+    let length = data.get(65).copied().unwrap_or(56);
+
+    let mut rtc = Ds1307::new(RegisterImage {
+        registers,
+        pointer: 0,
+    });
+
+    let _ = rtc.datetime();
+    let _ = rtc.date();
+    let _ = rtc.time();
+    let _ = rtc.seconds();
+    let _ = rtc.minutes();
+    let _ = rtc.hours();
+    let _ = rtc.weekday();
+    let _ = rtc.day();
+    let _ = rtc.month();
+    let _ = rtc.year();
+    let _ = rtc.running();
+    let _ = rtc.square_wave_output_enabled();
+    let _ = rtc.square_wave_output_level();
+    let _ = rtc.square_wave_output_rate();
+    let _ = rtc.primitive_datetime();
+    let _ = rtc.civil_datetime();
+    let _ = rtc.instant();
+    if let Ok(earlier) = rtc.snapshot() {
+        if let Ok(later) = rtc.snapshot() {
+            let _ = later.duration_since(&earlier);
+        }
+    }
+
+    let mut ram = [0; 56];
+    let _ = rtc.read_ram(offset, &mut ram[..usize::from(length).min(56)]);
+
+    if let Ok(store) = KvStore::load(&mut rtc, offset, length) {
+        for key in 0..=u8::MAX {
+            let _ = store.get(key);
+        }
+        let _ = store.free();
+    }
+
+    if let Ok(log) = EventLog::open(&mut rtc, offset, length) {
+        let _ = log.len();
+        if let Ok(events) = log.iter(&mut rtc) {
+            let _ = events.count();
+        }
+    }
+});
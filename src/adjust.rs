@@ -0,0 +1,99 @@
+use crate::bcd::{decimal_to_packed_bcd, is_24h_format};
+use crate::datetime::RawDateTime;
+use crate::{BitFlags, Ds1307, Error, Register};
+use chrono::{Datelike, TimeDelta, Timelike};
+use embedded_hal::i2c::I2c;
+
+/// Number of attempts to adjust the time without a second boundary passing.
+const ADJUST_ATTEMPTS: usize = 3;
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Move the date and time forward or backward by the given amount.
+    ///
+    /// The date and time registers are read, the adjusted values computed and
+    /// only the registers that change are written back. If a second boundary
+    /// passed in between, this is retried up to 3 times, after which the values
+    /// are computed from a final reading taken right after the second changed.
+    /// Adjusting by whole minutes leaves the seconds register and therefore the
+    /// sub-second phase untouched. The clock halt bit, the 12/24-hour mode and
+    /// the numbering of the day-of-week register are preserved. Sub-second
+    /// parts of `delta` are truncated.
+    ///
+    /// Will return an `Error::InvalidInputData` if the device contains an invalid
+    /// date or the result lies outside of the supported range (2000-2099).
+    pub fn adjust(&mut self, delta: TimeDelta) -> Result<(), Error<E>> {
+        let mut attempt = 1;
+        let (current, adjusted) = loop {
+            let current = self.read_datetime_registers()?;
+            let adjusted = adjusted_registers(&current, delta).ok_or(Error::InvalidInputData)?;
+            let seconds = self.read_register(Register::SECONDS)?;
+            if seconds == current[0] {
+                break (current, adjusted);
+            }
+            if attempt == ADJUST_ATTEMPTS {
+                // The second just changed, so a fresh reading is not stale.
+                let current = self.read_datetime_registers()?;
+                let adjusted =
+                    adjusted_registers(&current, delta).ok_or(Error::InvalidInputData)?;
+                break (current, adjusted);
+            }
+            attempt += 1;
+        };
+        let first = match (0..7).find(|&i| current[i] != adjusted[i]) {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        let last = (0..7)
+            .rev()
+            .find(|&i| current[i] != adjusted[i])
+            .unwrap_or(first);
+        let mut payload = [0; 8];
+        payload[0] = Register::SECONDS + first as u8;
+        payload[1..last - first + 2].copy_from_slice(&adjusted[first..=last]);
+        self.write_registers(&payload[..last - first + 2])
+    }
+
+    /// Move the date and time forward or backward by the given number of seconds.
+    ///
+    /// See [`adjust()`](Ds1307::adjust).
+    pub fn adjust_by(&mut self, seconds: i32) -> Result<(), Error<E>> {
+        self.adjust(TimeDelta::seconds(seconds.into()))
+    }
+}
+
+/// Compute the date and time register values adjusted by `delta`.
+///
+/// Returns `None` if the current values are invalid or the result is out of range.
+fn adjusted_registers(current: &[u8; 7], delta: TimeDelta) -> Option<[u8; 7]> {
+    let datetime = RawDateTime::from_registers(current).to_naive_datetime()?;
+    let adjusted = datetime.checked_add_signed(delta)?;
+    if adjusted.year() < 2000 || adjusted.year() > 2099 {
+        return None;
+    }
+    let days = adjusted
+        .date()
+        .signed_duration_since(datetime.date())
+        .num_days();
+    let weekday = (i64::from(current[Register::DOW as usize]) - 1 + days).rem_euclid(7) + 1;
+    let hours = current[Register::HOURS as usize];
+    let hours = if is_24h_format(hours) {
+        decimal_to_packed_bcd(adjusted.hour() as u8)
+    } else {
+        let (pm, hour) = adjusted.hour12();
+        let am_pm = if pm { BitFlags::AM_PM } else { 0 };
+        BitFlags::H24_H12 | am_pm | decimal_to_packed_bcd(hour as u8)
+    };
+    Some([
+        decimal_to_packed_bcd(adjusted.second() as u8)
+            | current[Register::SECONDS as usize] & BitFlags::CH,
+        decimal_to_packed_bcd(adjusted.minute() as u8),
+        hours,
+        weekday as u8,
+        decimal_to_packed_bcd(adjusted.day() as u8),
+        decimal_to_packed_bcd(adjusted.month() as u8),
+        decimal_to_packed_bcd((adjusted.year() - 2000) as u8),
+    ])
+}
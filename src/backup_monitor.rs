@@ -0,0 +1,115 @@
+use crate::datetime::{from_rtc_epoch, to_rtc_epoch};
+use crate::ram_record::read_u32;
+use crate::{DateTimeAccess, Ds1307, Error, NaiveDateTime, TimeDelta};
+use embedded_hal::i2c::I2c;
+
+const SIGNATURE: [u8; 2] = [0xB4, 0x77];
+
+/// Estimated state of the backup supply
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackupStatus {
+    /// The date and time were kept while the main supply was off.
+    BatteryOk,
+    /// The user RAM was kept but the date and time were not.
+    BatteryLikelyFailed,
+    /// No valid data was found in the user RAM, either because the device
+    /// was never used or because the backup supply failed completely.
+    FirstPowerUp,
+}
+
+/// Heuristic health estimation of the backup supply (battery).
+///
+/// While running, a "last seen alive" timestamp must be periodically stored in
+/// the user RAM with [`heartbeat()`](BackupMonitor::heartbeat). On boot,
+/// [`backup_status()`](BackupMonitor::backup_status) combines the clock halt
+/// bit, the validity of the stored data and a comparison of the stored
+/// timestamp with the RTC time (and optionally an external time reference,
+/// e.g. from the network) to classify the backup supply state.
+///
+/// The data occupies [`BackupMonitor::SIZE`] bytes of user RAM starting at
+/// the configured offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackupMonitor {
+    address_offset: u8,
+    tolerance: TimeDelta,
+}
+
+impl BackupMonitor {
+    /// Number of bytes of user RAM used by the monitor.
+    pub const SIZE: u8 = 7;
+
+    /// Create a new instance using the user RAM at the given offset.
+    ///
+    /// `tolerance` is the maximum amount the RTC may lag behind an
+    /// external reference for the battery to be considered ok.
+    pub fn new(address_offset: u8, tolerance: TimeDelta) -> Self {
+        BackupMonitor {
+            address_offset,
+            tolerance,
+        }
+    }
+
+    /// Store the current date and time as "last seen alive" timestamp.
+    pub fn heartbeat<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let now = rtc.datetime()?;
+        let seconds = to_rtc_epoch(&now).ok_or(Error::InvalidInputData)?;
+        let mut data = [0; Self::SIZE as usize];
+        data[..2].copy_from_slice(&SIGNATURE);
+        data[2..6].copy_from_slice(&seconds.to_le_bytes());
+        rtc.write_record(self.address_offset, data)
+    }
+
+    /// Read the date and time of the last heartbeat, if valid.
+    pub fn last_alive<I2C, E>(
+        &self,
+        rtc: &mut Ds1307<I2C>,
+    ) -> Result<Option<NaiveDateTime>, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let data = rtc.read_record::<{ Self::SIZE as usize }>(self.address_offset)?;
+        Ok(data
+            .filter(|data| data[..2] == SIGNATURE)
+            .and_then(|data| from_rtc_epoch(read_u32(&data[2..6]))))
+    }
+
+    /// Estimate the state of the backup supply. This should be called on boot,
+    /// before the first heartbeat.
+    ///
+    /// The battery is considered to have failed if the stored data is valid but
+    /// the clock is halted, contains an invalid date, lies before the last
+    /// heartbeat or lags behind the `reference` date and time by more than the
+    /// tolerance.
+    pub fn backup_status<I2C, E>(
+        &self,
+        rtc: &mut Ds1307<I2C>,
+        reference: Option<NaiveDateTime>,
+    ) -> Result<BackupStatus, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let last_alive = match self.last_alive(rtc)? {
+            Some(last_alive) => last_alive,
+            None => return Ok(BackupStatus::FirstPowerUp),
+        };
+        if !rtc.running()? {
+            return Ok(BackupStatus::BatteryLikelyFailed);
+        }
+        let now = match rtc.datetime() {
+            Ok(now) => now,
+            Err(Error::InvalidInputData) => return Ok(BackupStatus::BatteryLikelyFailed),
+            Err(e) => return Err(e),
+        };
+        let lags_reference = reference
+            .map(|reference| reference.signed_duration_since(now) > self.tolerance)
+            .unwrap_or(false);
+        if now < last_alive || lags_reference {
+            Ok(BackupStatus::BatteryLikelyFailed)
+        } else {
+            Ok(BackupStatus::BatteryOk)
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Conversions between register values and decimal numbers.
+//!
+//! This module is not part of the public API. It is only exposed so that
+//! the codec can be exercised by property-based and fuzz tests.
+
+use crate::BitFlags;
+
+/// Returns whether the hours register value is in 24-hour format.
+pub fn is_24h_format(hours_data: u8) -> bool {
+    hours_data & BitFlags::H24_H12 == 0
+}
+
+/// Returns whether the hours register value (in 12-hour format) is AM.
+pub fn is_am(hours_data: u8) -> bool {
+    hours_data & BitFlags::AM_PM == 0
+}
+
+/// Removes the clock halt bit from the seconds register value.
+pub fn remove_ch_bit(value: u8) -> u8 {
+    value & !BitFlags::CH
+}
+
+/// Transforms a number in packed BCD format to decimal
+pub fn packed_bcd_to_decimal(bcd: u8) -> u8 {
+    (bcd >> 4) * 10 + (bcd & 0xF)
+}
+
+/// Transforms a decimal number to packed BCD format
+pub fn decimal_to_packed_bcd(dec: u8) -> u8 {
+    ((dec / 10) << 4) | (dec % 10)
+}
+
+/// Transforms the hours register value to the hour in 24-hour format
+///
+/// In 12-hour format, 12 AM is midnight (0) and 12 PM is noon (12).
+pub fn hours_register_to_h24(data: u8) -> u8 {
+    if is_24h_format(data) {
+        packed_bcd_to_decimal(data & !BitFlags::H24_H12)
+    } else {
+        let hour = packed_bcd_to_decimal(data & !(BitFlags::H24_H12 | BitFlags::AM_PM)) % 12;
+        if is_am(data) {
+            hour
+        } else {
+            hour + 12
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_convert_to_h24() {
+        assert_eq!(0, hours_register_to_h24(0b0000_0000));
+        assert_eq!(0, hours_register_to_h24(0b0100_0000));
+        assert_eq!(12, hours_register_to_h24(0b0110_0000));
+
+        assert_eq!(1, hours_register_to_h24(0b0000_0001));
+        assert_eq!(1, hours_register_to_h24(0b0100_0001));
+        assert_eq!(13, hours_register_to_h24(0b0110_0001));
+
+        assert_eq!(23, hours_register_to_h24(0b0010_0011));
+        assert_eq!(0, hours_register_to_h24(0b0101_0010));
+        assert_eq!(12, hours_register_to_h24(0b0111_0010));
+        assert_eq!(23, hours_register_to_h24(0b0111_0001));
+    }
+
+    #[test]
+    fn can_convert_packed_bcd_to_decimal() {
+        assert_eq!(0, packed_bcd_to_decimal(0b0000_0000));
+        assert_eq!(1, packed_bcd_to_decimal(0b0000_0001));
+        assert_eq!(9, packed_bcd_to_decimal(0b0000_1001));
+        assert_eq!(10, packed_bcd_to_decimal(0b0001_0000));
+        assert_eq!(11, packed_bcd_to_decimal(0b0001_0001));
+        assert_eq!(19, packed_bcd_to_decimal(0b0001_1001));
+        assert_eq!(20, packed_bcd_to_decimal(0b0010_0000));
+        assert_eq!(21, packed_bcd_to_decimal(0b0010_0001));
+        assert_eq!(59, packed_bcd_to_decimal(0b0101_1001));
+    }
+
+    #[test]
+    fn can_convert_decimal_to_packed_bcd() {
+        assert_eq!(0b0000_0000, decimal_to_packed_bcd(0));
+        assert_eq!(0b0000_0001, decimal_to_packed_bcd(1));
+        assert_eq!(0b0000_1001, decimal_to_packed_bcd(9));
+        assert_eq!(0b0001_0000, decimal_to_packed_bcd(10));
+        assert_eq!(0b0001_0001, decimal_to_packed_bcd(11));
+        assert_eq!(0b0001_1001, decimal_to_packed_bcd(19));
+        assert_eq!(0b0010_0000, decimal_to_packed_bcd(20));
+        assert_eq!(0b0010_0001, decimal_to_packed_bcd(21));
+        assert_eq!(0b0101_1001, decimal_to_packed_bcd(59));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::datetime::{from_rtc_epoch, to_rtc_epoch};
+use crate::ram_record::read_u32;
+use crate::{DateTimeAccess, Ds1307, Error, NaiveDateTime, TimeDelta};
+use embedded_hal::i2c::I2c;
+
+/// Persistent boot counter and uptime ledger stored in the user RAM.
+///
+/// The ledger keeps the number of boots, the date and time of the last
+/// heartbeat and the cumulative powered time, protected by a CRC.
+/// It occupies [`BootLedger::SIZE`] bytes of user RAM starting at the
+/// configured offset.
+///
+/// The date and time of the last heartbeat is used on the next boot to compute
+/// how long the device was powered off. The powered time is only accumulated
+/// between heartbeats so [`heartbeat()`](BootLedger::heartbeat) should be
+/// called periodically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootLedger {
+    address_offset: u8,
+    boot_count: u32,
+    last_heartbeat: Option<NaiveDateTime>,
+    powered_seconds: u32,
+    last_downtime: Option<TimeDelta>,
+    was_reset: bool,
+}
+
+impl BootLedger {
+    /// Number of bytes of user RAM used by the ledger.
+    pub const SIZE: u8 = 13;
+
+    /// Record a boot in the ledger stored at the given user RAM offset.
+    ///
+    /// Increments the boot counter, computes the downtime since the last
+    /// heartbeat and stores the current date and time as new heartbeat.
+    ///
+    /// If the stored ledger is invalid (e.g. on first use or after a backup
+    /// supply failure) it is reset. This can be checked with
+    /// [`was_reset()`](BootLedger::was_reset).
+    ///
+    /// Will return an `Error::InvalidInputData` if the ledger does not fit in
+    /// the user RAM at the given offset.
+    pub fn record_boot<I2C, E>(rtc: &mut Ds1307<I2C>, address_offset: u8) -> Result<Self, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let data = rtc.read_record::<{ Self::SIZE as usize }>(address_offset)?;
+        let now = rtc.datetime()?;
+        let mut ledger = BootLedger {
+            address_offset,
+            boot_count: 0,
+            last_heartbeat: None,
+            powered_seconds: 0,
+            last_downtime: None,
+            was_reset: true,
+        };
+        if let Some(data) = data {
+            ledger.boot_count = read_u32(&data[0..4]);
+            ledger.last_heartbeat = from_rtc_epoch(read_u32(&data[4..8]));
+            ledger.powered_seconds = read_u32(&data[8..12]);
+            ledger.was_reset = false;
+        }
+        ledger.boot_count = ledger.boot_count.saturating_add(1);
+        ledger.last_downtime = ledger
+            .last_heartbeat
+            .map(|heartbeat| now.signed_duration_since(heartbeat))
+            .filter(|downtime| *downtime >= TimeDelta::zero());
+        ledger.last_heartbeat = Some(now);
+        ledger.store(rtc)?;
+        Ok(ledger)
+    }
+
+    /// Store the current date and time as heartbeat and accumulate the
+    /// powered time since the previous heartbeat.
+    pub fn heartbeat<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let now = rtc.datetime()?;
+        if let Some(heartbeat) = self.last_heartbeat {
+            let elapsed = now.signed_duration_since(heartbeat).num_seconds();
+            if let Ok(elapsed) = u32::try_from(elapsed) {
+                self.powered_seconds = self.powered_seconds.saturating_add(elapsed);
+            }
+        }
+        self.last_heartbeat = Some(now);
+        self.store(rtc)
+    }
+
+    /// Number of boots recorded, including the current one.
+    pub fn boot_count(&self) -> u32 {
+        self.boot_count
+    }
+
+    /// Date and time of the last heartbeat.
+    pub fn last_heartbeat(&self) -> Option<NaiveDateTime> {
+        self.last_heartbeat
+    }
+
+    /// Time elapsed between the last heartbeat before this boot and the boot.
+    ///
+    /// Returns `None` if there was no valid heartbeat stored or if the clock
+    /// went backwards in the meantime.
+    pub fn last_downtime(&self) -> Option<TimeDelta> {
+        self.last_downtime
+    }
+
+    /// Cumulative powered time accumulated between heartbeats.
+    pub fn powered_time(&self) -> TimeDelta {
+        TimeDelta::seconds(self.powered_seconds.into())
+    }
+
+    /// Whether the stored ledger was invalid when recording the boot and
+    /// was therefore reset.
+    pub fn was_reset(&self) -> bool {
+        self.was_reset
+    }
+
+    fn store<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let heartbeat = self
+            .last_heartbeat
+            .and_then(|heartbeat| to_rtc_epoch(&heartbeat))
+            .unwrap_or(0);
+        let mut data = [0; Self::SIZE as usize];
+        data[0..4].copy_from_slice(&self.boot_count.to_le_bytes());
+        data[4..8].copy_from_slice(&heartbeat.to_le_bytes());
+        data[8..12].copy_from_slice(&self.powered_seconds.to_le_bytes());
+        rtc.write_record(self.address_offset, data)
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{DateTimeAccess, Ds1307, Error};
+use chrono::{Datelike, IsoWeek, NaiveDate, NaiveDateTime, Weekday};
+use embedded_hal::i2c::I2c;
+
+/// Calendar fields derived from a single date and time reading
+///
+/// Reading the fields through separate `Rtcc` getters could mix values from
+/// before and after midnight. See: [`Ds1307::calendar()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarView {
+    datetime: NaiveDateTime,
+}
+
+impl CalendarView {
+    /// Create a view of the given date and time.
+    pub fn new(datetime: NaiveDateTime) -> Self {
+        CalendarView { datetime }
+    }
+
+    /// Date and time the view was built from.
+    pub fn datetime(&self) -> NaiveDateTime {
+        self.datetime
+    }
+
+    /// Date the view was built from.
+    pub fn date(&self) -> NaiveDate {
+        self.datetime.date()
+    }
+
+    /// Day of the week.
+    pub fn weekday(&self) -> Weekday {
+        self.datetime.weekday()
+    }
+
+    /// Day of the year [1-366].
+    pub fn day_of_year(&self) -> u32 {
+        self.datetime.ordinal()
+    }
+
+    /// ISO 8601 week and week-based year.
+    ///
+    /// The first days of January may belong to the last week of the
+    /// previous year and the last days of December to week 1 of the next one.
+    pub fn iso_week(&self) -> IsoWeek {
+        self.datetime.iso_week()
+    }
+
+    /// Number of days in the current month [28-31].
+    pub fn days_in_month(&self) -> u32 {
+        self.last_day_of_month().day()
+    }
+
+    /// First day of the current month.
+    pub fn first_day_of_month(&self) -> NaiveDate {
+        self.date().with_day(1).unwrap_or_else(|| self.date())
+    }
+
+    /// Last day of the current month.
+    pub fn last_day_of_month(&self) -> NaiveDate {
+        let first = self.first_day_of_month();
+        let next_month = if first.month() == 12 {
+            NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+        } else {
+            first.with_month(first.month() + 1)
+        };
+        next_month
+            .and_then(|next_month| next_month.pred_opt())
+            .unwrap_or_else(|| self.date())
+    }
+
+    /// Whether the current day is the first day of the month.
+    pub fn is_first_day_of_month(&self) -> bool {
+        self.datetime.day() == 1
+    }
+
+    /// Whether the current day is the last day of the month.
+    pub fn is_last_day_of_month(&self) -> bool {
+        self.datetime.day() == self.days_in_month()
+    }
+
+    /// Number of days remaining in the current month, excluding the current day.
+    pub fn days_until_month_end(&self) -> u32 {
+        self.days_in_month() - self.datetime.day()
+    }
+
+    /// Whether the current year is a leap year.
+    pub fn is_leap_year(&self) -> bool {
+        NaiveDate::from_ymd_opt(self.datetime.year(), 2, 29).is_some()
+    }
+}
+
+impl From<NaiveDateTime> for CalendarView {
+    fn from(datetime: NaiveDateTime) -> Self {
+        CalendarView::new(datetime)
+    }
+}
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the date and time once and derive the calendar fields from it.
+    ///
+    /// Will return an `Error::InvalidInputData` if the device contains an invalid date.
+    pub fn calendar(&mut self) -> Result<CalendarView, Error<E>> {
+        self.datetime().map(CalendarView::new)
+    }
+}
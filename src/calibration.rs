@@ -0,0 +1,65 @@
+use crate::SqwOutRate;
+
+/// Measurement of a clock against the square-wave output.
+///
+/// Count the ticks of a clock (e.g. an MCU timer) over a number of
+/// square-wave output periods to determine its actual frequency and
+/// its error with respect to the RTC crystal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    rate: SqwOutRate,
+    periods: u32,
+    ticks: u32,
+}
+
+impl Calibration {
+    /// Create a new measurement from the number of `ticks` counted over
+    /// `periods` square-wave output periods at `rate`.
+    ///
+    /// Returns `None` if `periods` is zero.
+    pub fn new(rate: SqwOutRate, periods: u32, ticks: u32) -> Option<Self> {
+        if periods == 0 {
+            return None;
+        }
+        Some(Calibration {
+            rate,
+            periods,
+            ticks,
+        })
+    }
+
+    /// Measured frequency of the clock in Hz, rounded to the nearest integer.
+    ///
+    /// Saturates at `u32::MAX`.
+    pub fn measured_hz(&self) -> u32 {
+        let ticks = u64::from(self.ticks) * u64::from(self.rate.hz());
+        let periods = u64::from(self.periods);
+        u32::try_from((ticks + periods / 2) / periods).unwrap_or(u32::MAX)
+    }
+
+    /// Measured frequency of the clock as a `fugit` rate.
+    #[cfg(feature = "fugit")]
+    pub fn measured_rate(&self) -> fugit::HertzU32 {
+        fugit::HertzU32::from_raw(self.measured_hz())
+    }
+
+    /// Error of the clock with respect to its `nominal_hz` frequency in
+    /// parts per million, rounded to the nearest integer.
+    ///
+    /// A positive value means the clock runs faster than nominal.
+    /// Returns `None` if `nominal_hz` is zero.
+    pub fn error_ppm(&self, nominal_hz: u32) -> Option<i32> {
+        if nominal_hz == 0 {
+            return None;
+        }
+        let measured = i128::from(self.ticks) * i128::from(self.rate.hz());
+        let expected = i128::from(nominal_hz) * i128::from(self.periods);
+        let error = (measured - expected) * 1_000_000;
+        let ppm = if error < 0 {
+            (error - expected / 2) / expected
+        } else {
+            (error + expected / 2) / expected
+        };
+        Some(ppm.clamp(i128::from(i32::MIN), i128::from(i32::MAX)) as i32)
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{DateTimeAccess, Ds1307, Error, NaiveDateTime, TimeDelta};
+use embedded_hal::i2c::I2c;
+
+/// Access to the system clock of the host.
+///
+/// All date and time values are expected to be in the same time zone as the
+/// RTC, typically UTC.
+pub trait SystemClock {
+    /// System clock error type.
+    type Error;
+
+    /// Read the current system date and time.
+    fn now(&mut self) -> Result<NaiveDateTime, Self::Error>;
+
+    /// Set the system date and time.
+    fn set(&mut self, datetime: &NaiveDateTime) -> Result<(), Self::Error>;
+}
+
+/// Policy for setting the system time from the RTC at startup
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum StartupPolicy {
+    /// Never set the system time.
+    Never,
+    /// Always set the system time.
+    #[default]
+    Always,
+    /// Set the system time only if it lies behind the RTC time.
+    IfSystemBehind,
+    /// Set the system time only if it differs from the RTC time by more than
+    /// the given number of seconds.
+    IfDriftExceeds(u32),
+}
+
+/// Errors synchronizing the RTC and the system clock
+#[derive(Debug)]
+pub enum SyncError<E, C> {
+    /// RTC error.
+    Rtc(Error<E>),
+    /// System clock error.
+    SystemClock(C),
+}
+
+/// Result of a synchronization between the RTC and the system clock
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncReport {
+    /// RTC date and time before the synchronization.
+    pub rtc: NaiveDateTime,
+    /// System date and time before the synchronization.
+    pub system: NaiveDateTime,
+    /// Whether the system time was set from the RTC.
+    pub system_updated: bool,
+    /// Whether the RTC was set from the system time.
+    pub rtc_updated: bool,
+}
+
+impl SyncReport {
+    /// Difference between the RTC and the system time. Positive values mean
+    /// the RTC is ahead of the system clock.
+    pub fn drift(&self) -> TimeDelta {
+        self.rtc.signed_duration_since(self.system)
+    }
+}
+
+/// Synchronization between the RTC and the system clock of a host
+///
+/// At startup, the system time is set from the RTC according to the
+/// configured [`StartupPolicy`] (like `hwclock --hctosys`). Afterwards the
+/// system time should be periodically written to the RTC (like
+/// `hwclock --systohc`) with [`system_to_rtc()`](ClockSync::system_to_rtc).
+/// Both operations report the drift between both clocks.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ClockSync {
+    policy: StartupPolicy,
+}
+
+impl ClockSync {
+    /// Create a new instance with the given startup policy.
+    pub fn new(policy: StartupPolicy) -> Self {
+        ClockSync { policy }
+    }
+
+    /// Set the system time from the RTC according to the startup policy.
+    pub fn rtc_to_system<I2C, E, C>(
+        &self,
+        rtc: &mut Ds1307<I2C>,
+        clock: &mut C,
+    ) -> Result<SyncReport, SyncError<E, C::Error>>
+    where
+        I2C: I2c<Error = E>,
+        C: SystemClock,
+    {
+        let (rtc_time, system_time) = read_both(rtc, clock)?;
+        let drift = rtc_time.signed_duration_since(system_time);
+        let update = match self.policy {
+            StartupPolicy::Never => false,
+            StartupPolicy::Always => true,
+            StartupPolicy::IfSystemBehind => drift > TimeDelta::zero(),
+            StartupPolicy::IfDriftExceeds(seconds) => drift.num_seconds().abs() > seconds.into(),
+        };
+        if update {
+            clock.set(&rtc_time).map_err(SyncError::SystemClock)?;
+        }
+        Ok(SyncReport {
+            rtc: rtc_time,
+            system: system_time,
+            system_updated: update,
+            rtc_updated: false,
+        })
+    }
+
+    /// Write the system time to the RTC.
+    pub fn system_to_rtc<I2C, E, C>(
+        &self,
+        rtc: &mut Ds1307<I2C>,
+        clock: &mut C,
+    ) -> Result<SyncReport, SyncError<E, C::Error>>
+    where
+        I2C: I2c<Error = E>,
+        C: SystemClock,
+    {
+        let (rtc_time, system_time) = read_both(rtc, clock)?;
+        rtc.set_datetime(&system_time).map_err(SyncError::Rtc)?;
+        Ok(SyncReport {
+            rtc: rtc_time,
+            system: system_time,
+            system_updated: false,
+            rtc_updated: true,
+        })
+    }
+}
+
+fn read_both<I2C, E, C>(
+    rtc: &mut Ds1307<I2C>,
+    clock: &mut C,
+) -> Result<(NaiveDateTime, NaiveDateTime), SyncError<E, C::Error>>
+where
+    I2C: I2c<Error = E>,
+    C: SystemClock,
+{
+    let rtc_time = rtc.datetime().map_err(SyncError::Rtc)?;
+    let system_time = clock.now().map_err(SyncError::SystemClock)?;
+    Ok((rtc_time, system_time))
+}
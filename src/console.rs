@@ -0,0 +1,266 @@
+use crate::ram::RAM_BYTE_COUNT;
+use crate::{format_datetime, DateTimeAccess, Ds1307, Error, SqwOutLevel, SqwOutRate, TimeInput};
+use core::fmt::{self, Write};
+use embedded_hal::i2c::I2c;
+
+/// Maximum length of the `set` command input.
+const MAX_TIME_INPUT_LENGTH: usize = 32;
+
+const HELP: &str = "\
+time                 print the date and time
+set <input>          set the date and time, e.g. 2024-02-01 13:45, 1:45pm or +90s
+run | halt           start or stop the clock
+status               print the clock and square-wave output state
+sqw on|off           enable or disable the square-wave output
+sqw <hz>             set the square-wave rate (1, 4096, 8192, 32768)
+sqw low|high         set the output level while the square-wave is disabled
+ram dump [off [len]] print the user RAM contents
+ram poke <off> <b>.. write bytes to the user RAM
+";
+
+/// Errors executing a console command
+#[derive(Debug)]
+pub enum ConsoleError<E> {
+    /// RTC error.
+    Rtc(Error<E>),
+    /// The command is not known.
+    UnknownCommand,
+    /// An argument is missing or invalid.
+    InvalidArgument,
+    /// Writing the response failed.
+    Output,
+}
+
+impl<E> From<Error<E>> for ConsoleError<E> {
+    fn from(e: Error<E>) -> Self {
+        ConsoleError::Rtc(e)
+    }
+}
+
+impl<E> From<fmt::Error> for ConsoleError<E> {
+    fn from(_: fmt::Error) -> Self {
+        ConsoleError::Output
+    }
+}
+
+/// Text command interpreter for a serial console or shell.
+///
+/// Each command line is parsed, executed on the RTC and the response written
+/// to a [`core::fmt::Write`]. The `help` command lists the available commands.
+/// Numbers may be given in decimal or in hexadecimal with a `0x` prefix.
+///
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use ds1307::{Console, Ds1307};
+/// use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+///
+/// let mut rtc = Ds1307::new(Mock::new(&[
+///     Transaction::write_read(0x68, vec![0x08], vec![0xAB, 0xCD]),
+/// ]));
+/// let mut out = String::new();
+/// Console::new().execute(&mut rtc, "ram dump 0 2", &mut out).unwrap();
+/// assert_eq!("00: ab cd\n", out);
+/// # rtc.destroy().done();
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Console<'a> {
+    format: &'a str,
+}
+
+impl Default for Console<'_> {
+    fn default() -> Self {
+        Console::new()
+    }
+}
+
+impl<'a> Console<'a> {
+    /// Create a new console printing the date and time as `%Y-%m-%d %H:%M:%S`.
+    pub fn new() -> Self {
+        Console {
+            format: "%Y-%m-%d %H:%M:%S",
+        }
+    }
+
+    /// Set the pattern used to print the date and time.
+    ///
+    /// See [`format_datetime()`] for the available specifiers.
+    pub fn with_format(format: &'a str) -> Self {
+        Console { format }
+    }
+
+    /// Execute a command line and write the response to `out`.
+    ///
+    /// The command and its arguments are separated by any ASCII whitespace.
+    /// Empty lines are ignored.
+    pub fn execute<I2C, E, W>(
+        &self,
+        rtc: &mut Ds1307<I2C>,
+        line: &str,
+        out: &mut W,
+    ) -> Result<(), ConsoleError<E>>
+    where
+        I2C: I2c<Error = E>,
+        W: Write,
+    {
+        let line = line.trim_matches(|c: char| c.is_ascii_whitespace());
+        let (command, arguments) = line
+            .split_once(|c: char| c.is_ascii_whitespace())
+            .unwrap_or((line, ""));
+        let arguments = arguments.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        match command {
+            "" => Ok(()),
+            "help" => Ok(out.write_str(HELP)?),
+            "time" if arguments.is_empty() => self.print_time(rtc, out),
+            "set" => {
+                let input = parse_time_input(arguments).ok_or(ConsoleError::InvalidArgument)?;
+                rtc.set_time_input(&input)?;
+                self.print_time(rtc, out)
+            }
+            "run" if arguments.is_empty() => Ok(rtc.set_running()?),
+            "halt" if arguments.is_empty() => Ok(rtc.halt()?),
+            "status" if arguments.is_empty() => print_status(rtc, out),
+            "sqw" => square_wave(rtc, arguments),
+            "ram" => ram(rtc, arguments, out),
+            "time" | "run" | "halt" | "status" => Err(ConsoleError::InvalidArgument),
+            _ => Err(ConsoleError::UnknownCommand),
+        }
+    }
+
+    fn print_time<I2C, E, W>(
+        &self,
+        rtc: &mut Ds1307<I2C>,
+        out: &mut W,
+    ) -> Result<(), ConsoleError<E>>
+    where
+        I2C: I2c<Error = E>,
+        W: Write,
+    {
+        let datetime = rtc.datetime()?;
+        format_datetime(out, &datetime, self.format)?;
+        Ok(out.write_char('\n')?)
+    }
+}
+
+fn print_status<I2C, E, W>(rtc: &mut Ds1307<I2C>, out: &mut W) -> Result<(), ConsoleError<E>>
+where
+    I2C: I2c<Error = E>,
+    W: Write,
+{
+    let running = rtc.running()?;
+    let control = rtc.control()?;
+    let level = match control.out_level {
+        SqwOutLevel::Low => "low",
+        SqwOutLevel::High => "high",
+    };
+    writeln!(
+        out,
+        "clock: {}\nsqw: {}, {} Hz, level {}",
+        if running { "running" } else { "halted" },
+        if control.sqwe { "on" } else { "off" },
+        control.rate.hz(),
+        level
+    )?;
+    Ok(())
+}
+
+fn square_wave<I2C, E>(rtc: &mut Ds1307<I2C>, argument: &str) -> Result<(), ConsoleError<E>>
+where
+    I2C: I2c<Error = E>,
+{
+    match argument {
+        "on" => rtc.enable_square_wave_output()?,
+        "off" => rtc.disable_square_wave_output()?,
+        "low" => rtc.set_square_wave_output_level(SqwOutLevel::Low)?,
+        "high" => rtc.set_square_wave_output_level(SqwOutLevel::High)?,
+        _ => {
+            let rate = parse_number(argument)
+                .and_then(SqwOutRate::from_hz)
+                .ok_or(ConsoleError::InvalidArgument)?;
+            rtc.set_square_wave_output_rate(rate)?
+        }
+    }
+    Ok(())
+}
+
+fn ram<I2C, E, W>(
+    rtc: &mut Ds1307<I2C>,
+    arguments: &str,
+    out: &mut W,
+) -> Result<(), ConsoleError<E>>
+where
+    I2C: I2c<Error = E>,
+    W: Write,
+{
+    let mut arguments = arguments.split_ascii_whitespace();
+    let subcommand = arguments.next();
+    let mut numbers = arguments.map(|argument| {
+        parse_number(argument)
+            .and_then(|n| u8::try_from(n).ok())
+            .ok_or(ConsoleError::InvalidArgument)
+    });
+    let mut data = [0; RAM_BYTE_COUNT];
+    match subcommand {
+        Some("dump") => {
+            let offset = numbers.next().transpose()?.unwrap_or(0);
+            if usize::from(offset) >= RAM_BYTE_COUNT {
+                return Err(ConsoleError::InvalidArgument);
+            }
+            let length = match numbers.next().transpose()? {
+                Some(length) => usize::from(length),
+                None => RAM_BYTE_COUNT - usize::from(offset),
+            };
+            if numbers.next().is_some() || length > RAM_BYTE_COUNT {
+                return Err(ConsoleError::InvalidArgument);
+            }
+            let data = &mut data[..length];
+            rtc.read_ram(offset, data)?;
+            for (index, line) in data.chunks(16).enumerate() {
+                write!(out, "{:02x}:", usize::from(offset) + index * 16)?;
+                for byte in line {
+                    write!(out, " {byte:02x}")?;
+                }
+                out.write_char('\n')?;
+            }
+            Ok(())
+        }
+        Some("poke") => {
+            let offset = numbers.next().ok_or(ConsoleError::InvalidArgument)??;
+            let mut length = 0;
+            for byte in numbers {
+                *data.get_mut(length).ok_or(ConsoleError::InvalidArgument)? = byte?;
+                length += 1;
+            }
+            if length == 0 {
+                return Err(ConsoleError::InvalidArgument);
+            }
+            Ok(rtc.write_ram(offset, &data[..length])?)
+        }
+        _ => Err(ConsoleError::InvalidArgument),
+    }
+}
+
+/// Parse a time input with its parts separated by any ASCII whitespace.
+fn parse_time_input(text: &str) -> Option<TimeInput> {
+    let mut buffer = [0; MAX_TIME_INPUT_LENGTH];
+    let mut length = 0;
+    for part in text.split_ascii_whitespace() {
+        if length > 0 {
+            *buffer.get_mut(length)? = b' ';
+            length += 1;
+        }
+        buffer
+            .get_mut(length..length + part.len())?
+            .copy_from_slice(part.as_bytes());
+        length += part.len();
+    }
+    core::str::from_utf8(&buffer[..length]).ok()?.parse().ok()
+}
+
+fn parse_number(text: &str) -> Option<u32> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
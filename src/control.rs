@@ -0,0 +1,113 @@
+use crate::{BitFlags, Ds1307, Error, Register, SqwOutLevel, SqwOutRate};
+use embedded_hal::i2c::I2c;
+
+/// Contents of the control register
+///
+/// The default value corresponds to the device power-on state: square-wave
+/// output disabled, output level low and 32.768 kHz rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ControlConfig {
+    /// Whether the square-wave output is enabled.
+    pub sqwe: bool,
+    /// Output level while the square-wave output is disabled.
+    pub out_level: SqwOutLevel,
+    /// Square-wave output rate.
+    pub rate: SqwOutRate,
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        ControlConfig {
+            sqwe: false,
+            out_level: SqwOutLevel::Low,
+            rate: SqwOutRate::Khz32_768,
+        }
+    }
+}
+
+impl ControlConfig {
+    fn from_register(data: u8) -> Self {
+        ControlConfig {
+            sqwe: data & BitFlags::SQWE != 0,
+            out_level: if data & BitFlags::OUTLEVEL != 0 {
+                SqwOutLevel::High
+            } else {
+                SqwOutLevel::Low
+            },
+            rate: SqwOutRate::from_register(data),
+        }
+    }
+
+    fn to_register(self) -> u8 {
+        let mut data = self.rate.register_bits();
+        if self.sqwe {
+            data |= BitFlags::SQWE;
+        }
+        if self.out_level == SqwOutLevel::High {
+            data |= BitFlags::OUTLEVEL;
+        }
+        data
+    }
+}
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the control register configuration in one transaction.
+    pub fn control(&mut self) -> Result<ControlConfig, Error<E>> {
+        let data = self.read_register(Register::SQWOUT)?;
+        Ok(ControlConfig::from_register(data))
+    }
+
+    /// Write the complete control register configuration in one transaction.
+    pub fn set_control(&mut self, config: ControlConfig) -> Result<(), Error<E>> {
+        self.write_register(Register::SQWOUT, config.to_register())
+    }
+
+    /// Configure the control register starting from the power-on state.
+    ///
+    /// Nothing is written until calling [`ControlBuilder::apply()`].
+    pub fn configure(&mut self) -> ControlBuilder<'_, I2C> {
+        ControlBuilder {
+            rtc: self,
+            config: ControlConfig::default(),
+        }
+    }
+}
+
+/// Builder for the control register configuration. See [`Ds1307::configure()`].
+#[derive(Debug)]
+pub struct ControlBuilder<'a, I2C> {
+    rtc: &'a mut Ds1307<I2C>,
+    config: ControlConfig,
+}
+
+impl<'a, I2C, E> ControlBuilder<'a, I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Enable or disable the square-wave output.
+    pub fn square_wave_output(mut self, enabled: bool) -> Self {
+        self.config.sqwe = enabled;
+        self
+    }
+
+    /// Set the output level while the square-wave output is disabled.
+    pub fn output_level(mut self, level: SqwOutLevel) -> Self {
+        self.config.out_level = level;
+        self
+    }
+
+    /// Set the square-wave output rate.
+    pub fn output_rate(mut self, rate: SqwOutRate) -> Self {
+        self.config.rate = rate;
+        self
+    }
+
+    /// Write the configuration to the device in one transaction.
+    pub fn apply(self) -> Result<ControlConfig, Error<E>> {
+        self.rtc.set_control(self.config)?;
+        Ok(self.config)
+    }
+}
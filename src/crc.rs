@@ -0,0 +1,30 @@
+/// CRC-8 (polynomial 0x31, initial value 0xFF) used to protect data stored in user RAM.
+///
+/// The non-zero initial value makes sure that all-zero data does not have a valid CRC.
+pub(crate) fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0xFF;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            if crc & 0x80 != 0 {
+                crc = (crc << 1) ^ 0x31;
+            } else {
+                crc <<= 1;
+            }
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_compute_crc8() {
+        assert_eq!(0xFF, crc8(&[]));
+        assert_eq!(0xF7, crc8(b"123456789"));
+        assert_ne!(0, crc8(&[0; 12]));
+        assert_ne!(crc8(&[0, 1]), crc8(&[1, 0]));
+    }
+}
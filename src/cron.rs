@@ -0,0 +1,291 @@
+use crate::ram_record::DATETIME_RECORD_SIZE;
+use crate::{DateTimeAccess, Ds1307, Error};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use core::str::FromStr;
+use embedded_hal::i2c::I2c;
+
+/// Maximum number of years searched for the next fire time.
+const SEARCH_YEARS: i64 = 8;
+
+/// Errors parsing a cron expression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CronError {
+    /// The expression does not consist of exactly 5 fields.
+    FieldCount,
+    /// The field at the given index [0-4] is malformed or out of range.
+    InvalidField(u8),
+}
+
+/// Cron schedule with minute resolution
+///
+/// Parsed from the usual 5 fields: minute [0-59], hour [0-23], day of the
+/// month [1-31], month [1-12] and day of the week [0-7] (0 and 7 are Sunday).
+/// Each field is `*` or a comma-separated list of values `n` and ranges `n-m`,
+/// all optionally followed by a step `/s`. As in classic cron, if both the day
+/// of the month and the day of the week are restricted, a day matching either
+/// of them fires.
+///
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use ds1307::{CronSchedule, NaiveDate};
+///
+/// // Every Monday to Friday at 06:30
+/// let schedule: CronSchedule = "30 6 * * 1-5".parse().unwrap();
+/// let friday = NaiveDate::from_ymd_opt(2024, 2, 2).unwrap();
+/// let after = friday.and_hms_opt(7, 0, 0).unwrap();
+/// let monday = NaiveDate::from_ymd_opt(2024, 2, 5).unwrap();
+/// assert_eq!(Some(monday.and_hms_opt(6, 30, 0).unwrap()), schedule.next_fire(after));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression.
+    pub fn parse(expression: &str) -> Result<Self, CronError> {
+        let mut fields = expression.split_ascii_whitespace();
+        let mut masks = [0; 5];
+        let mut any = [false; 5];
+        let ranges = [(0, 59), (0, 23), (1, 31), (1, 12), (0, 7)];
+        for (index, (min, max)) in ranges.iter().enumerate() {
+            let field = fields.next().ok_or(CronError::FieldCount)?;
+            masks[index] =
+                parse_field(field, *min, *max).ok_or(CronError::InvalidField(index as u8))?;
+            any[index] = field.starts_with('*');
+        }
+        if fields.next().is_some() {
+            return Err(CronError::FieldCount);
+        }
+        // Sunday may be written as 7.
+        let weekdays = (masks[4] | masks[4] >> 7) & 0x7F;
+        Ok(CronSchedule {
+            minutes: masks[0],
+            hours: masks[1],
+            days: masks[2],
+            months: masks[3],
+            weekdays,
+            any_day: any[2],
+            any_weekday: any[4],
+        })
+    }
+
+    /// Whether the schedule fires at the minute of the given date and time.
+    pub fn matches(&self, datetime: &NaiveDateTime) -> bool {
+        self.matches_date(&datetime.date())
+            && has_bit(self.hours, datetime.hour())
+            && has_bit(self.minutes, datetime.minute())
+    }
+
+    /// Earliest date and time strictly after `after` at which the schedule fires.
+    ///
+    /// Returns `None` if the schedule does not fire within the following 8 years
+    /// (e.g. on February 30th).
+    pub fn next_fire(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let end = after.checked_add_signed(TimeDelta::days(366 * SEARCH_YEARS))?;
+        let mut next = after
+            .date()
+            .and_hms_opt(after.hour(), after.minute(), 0)?
+            .checked_add_signed(TimeDelta::minutes(1))?;
+        while next <= end {
+            let date = next.date();
+            if !has_bit(self.months, date.month()) {
+                let (year, month) = if date.month() == 12 {
+                    (date.year() + 1, 1)
+                } else {
+                    (date.year(), date.month() + 1)
+                };
+                next = NaiveDate::from_ymd_opt(year, month, 1)?.and_time(NaiveTime::MIN);
+            } else if !self.matches_date(&date) {
+                next = date.succ_opt()?.and_time(NaiveTime::MIN);
+            } else if !has_bit(self.hours, next.hour()) {
+                next = date.and_hms_opt(next.hour(), 0, 0)? + TimeDelta::hours(1);
+            } else if !has_bit(self.minutes, next.minute()) {
+                next += TimeDelta::minutes(1);
+            } else {
+                return Some(next);
+            }
+        }
+        None
+    }
+
+    fn matches_date(&self, date: &NaiveDate) -> bool {
+        let day = has_bit(self.days, date.day());
+        let weekday = has_bit(self.weekdays, date.weekday().num_days_from_sunday());
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+        day_matches && has_bit(self.months, date.month())
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CronSchedule::parse(s)
+    }
+}
+
+fn has_bit(mask: u64, bit: u32) -> bool {
+    mask & (1 << bit) != 0
+}
+
+fn parse_field(field: &str, min: u8, max: u8) -> Option<u64> {
+    let mut mask = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u8>().ok().filter(|s| *s > 0)?),
+            None => (item, 1),
+        };
+        let (first, last) = if range == "*" {
+            (min, max)
+        } else if let Some((first, last)) = range.split_once('-') {
+            (first.parse().ok()?, last.parse().ok()?)
+        } else {
+            let value = range.parse().ok()?;
+            (value, if step > 1 { max } else { value })
+        };
+        if first < min || last > max || first > last {
+            return None;
+        }
+        for value in (first..=last).step_by(usize::from(step)) {
+            mask |= 1 << value;
+        }
+    }
+    Some(mask)
+}
+
+/// Set of schedules found due by [`Scheduler::due_jobs()`]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DueJobs(u32);
+
+impl DueJobs {
+    /// Whether the schedule at the given index is due.
+    pub fn contains(&self, index: usize) -> bool {
+        index < Scheduler::MAX_JOBS && self.0 & (1 << index) != 0
+    }
+
+    /// Whether no schedule is due.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Number of due schedules.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Iterate over the indices of the due schedules.
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let mask = self.0;
+        (0..Scheduler::MAX_JOBS).filter(move |index| mask & (1 << index) != 0)
+    }
+}
+
+/// Evaluation of cron schedules against the RTC time.
+///
+/// Every call to [`due_jobs()`](Scheduler::due_jobs) reads the RTC once and
+/// reports the schedules which fired since the previous check. The time of the
+/// last check can optionally be persisted in the user RAM, so that schedules
+/// which fired while the system was off are reported on the first check after
+/// power up. It then occupies [`Scheduler::SIZE`] bytes of user RAM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scheduler<'a> {
+    jobs: &'a [CronSchedule],
+    last_check: Option<NaiveDateTime>,
+    address_offset: Option<u8>,
+}
+
+impl<'a> Scheduler<'a> {
+    /// Maximum number of schedules.
+    pub const MAX_JOBS: usize = 32;
+
+    /// Number of bytes of user RAM used when persisting the last check time.
+    pub const SIZE: u8 = DATETIME_RECORD_SIZE;
+
+    /// Create a new scheduler without persistence.
+    ///
+    /// Returns `None` if there are more than [`Scheduler::MAX_JOBS`] schedules.
+    pub fn new(jobs: &'a [CronSchedule]) -> Option<Self> {
+        if jobs.len() > Self::MAX_JOBS {
+            return None;
+        }
+        Some(Scheduler {
+            jobs,
+            last_check: None,
+            address_offset: None,
+        })
+    }
+
+    /// Create a new scheduler persisting the last check time in the user RAM
+    /// at the given offset, loading the previously stored value.
+    ///
+    /// An invalid stored value (e.g. on first use) is ignored.
+    ///
+    /// Will return an `Error::InvalidInputData` if there are more than
+    /// [`Scheduler::MAX_JOBS`] schedules or the stored value does not fit in
+    /// the user RAM at the given offset.
+    pub fn load<I2C, E>(
+        rtc: &mut Ds1307<I2C>,
+        address_offset: u8,
+        jobs: &'a [CronSchedule],
+    ) -> Result<Self, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let mut scheduler = Self::new(jobs).ok_or(Error::InvalidInputData)?;
+        scheduler.last_check = rtc.read_datetime_record(address_offset)?;
+        scheduler.address_offset = Some(address_offset);
+        Ok(scheduler)
+    }
+
+    /// Read the RTC time and return the schedules which fired since the
+    /// previous check.
+    ///
+    /// Nothing is due on the first check without a stored last check time or
+    /// if the clock went backwards since.
+    pub fn due_jobs<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>) -> Result<DueJobs, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let now = rtc.datetime()?;
+        let mut due = DueJobs::default();
+        if let Some(last_check) = self.last_check {
+            for (index, job) in self.jobs.iter().enumerate() {
+                if job.next_fire(last_check).map_or(false, |fire| fire <= now) {
+                    due.0 |= 1 << index;
+                }
+            }
+        }
+        if self.last_check != Some(now) {
+            self.last_check = Some(now);
+            self.store(rtc)?;
+        }
+        Ok(due)
+    }
+
+    /// Date and time of the last check.
+    pub fn last_check(&self) -> Option<NaiveDateTime> {
+        self.last_check
+    }
+
+    fn store<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        match self.address_offset {
+            Some(address_offset) => rtc.write_datetime_record(address_offset, self.last_check),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,52 +1,167 @@
-use crate::{BitFlags, Ds1307, Error, Register, ADDR};
+// Without any date/time library there is no user of the raw date/time access.
+#![cfg_attr(
+    not(any(feature = "chrono", feature = "time", feature = "jiff")),
+    allow(dead_code)
+)]
+
+use crate::bcd::{
+    decimal_to_packed_bcd, hours_register_to_h24, packed_bcd_to_decimal, remove_ch_bit,
+};
+#[cfg(feature = "chrono")]
+use crate::bcd::{is_24h_format, is_am};
+#[cfg(feature = "chrono")]
+use crate::WeekdayConvention;
+use crate::{BitFlags, Ds1307, Error, Register};
 use embedded_hal::i2c::I2c;
+#[cfg(feature = "chrono")]
 pub use rtcc::{
     DateTimeAccess, Datelike, Hours, NaiveDate, NaiveDateTime, NaiveTime, Rtcc, Timelike,
 };
 
-impl<I2C, E> DateTimeAccess for Ds1307<I2C>
+/// Date and time as stored in the device registers, independent of
+/// any date/time library.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RawDateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    /// Day of the week [1-7] according to the weekday convention.
+    pub weekday: u8,
+    /// Hour in 24-hour format.
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl RawDateTime {
+    pub(crate) fn from_registers(data: &[u8; 7]) -> Self {
+        RawDateTime {
+            year: 2000 + u16::from(packed_bcd_to_decimal(data[Register::YEAR as usize])),
+            month: packed_bcd_to_decimal(data[Register::MONTH as usize]),
+            day: packed_bcd_to_decimal(data[Register::DOM as usize]),
+            weekday: packed_bcd_to_decimal(data[Register::DOW as usize]),
+            hour: hours_register_to_h24(data[Register::HOURS as usize]),
+            minute: packed_bcd_to_decimal(data[Register::MINUTES as usize]),
+            second: packed_bcd_to_decimal(remove_ch_bit(data[Register::SECONDS as usize])),
+        }
+    }
+}
+
+impl<I2C, E> Ds1307<I2C>
 where
     I2C: I2c<Error = E>,
 {
-    type Error = Error<E>;
+    /// Read all date and time registers at once.
+    pub(crate) fn read_raw_datetime(&mut self) -> Result<RawDateTime, Error<E>> {
+        let data = self.read_datetime_registers()?;
+        Ok(RawDateTime::from_registers(&data))
+    }
 
-    fn datetime(&mut self) -> Result<NaiveDateTime, Self::Error> {
+    /// Read the raw contents of all date and time registers at once.
+    pub(crate) fn read_datetime_registers(&mut self) -> Result<[u8; 7], Error<E>> {
         let mut data = [0; 7];
-        self.i2c
-            .write_read(ADDR, &[0x00], &mut data)
-            .map_err(Error::I2C)?;
-        let year = 2000 + u16::from(packed_bcd_to_decimal(data[Register::YEAR as usize]));
-        let month = packed_bcd_to_decimal(data[Register::MONTH as usize]);
-        let day = packed_bcd_to_decimal(data[Register::DOM as usize]);
-        let hour = self.get_hours_from_register(data[Register::HOURS as usize])?;
-        let minute = packed_bcd_to_decimal(data[Register::MINUTES as usize]);
-        let second = packed_bcd_to_decimal(remove_ch_bit(data[Register::SECONDS as usize]));
-        let date = NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
-            .ok_or(Error::InvalidInputData)?;
-        date.and_hms_opt(get_h24(hour).into(), minute.into(), second.into())
-            .ok_or(Error::InvalidInputData)
+        self.read_registers(Register::SECONDS, &mut data)?;
+        Ok(data)
     }
 
-    fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Self::Error> {
-        if datetime.year() < 2000 || datetime.year() > 2099 {
+    /// Write all date and time registers at once, keeping the CH bit status.
+    ///
+    /// The date and time must have been validated before apart from the year.
+    pub(crate) fn write_raw_datetime(&mut self, datetime: &RawDateTime) -> Result<(), Error<E>> {
+        if datetime.year < 2000 || datetime.year > 2099 {
+            return Err(Error::InvalidInputData);
+        }
+        if datetime.hour > 23 {
             return Err(Error::InvalidInputData);
         }
-        let hour = self.get_hours_register_value(Hours::H24(datetime.hour() as u8))?;
         let ch_flag = self.read_register(Register::SECONDS)? & BitFlags::CH;
         let payload = [
             Register::SECONDS,
-            decimal_to_packed_bcd(datetime.second() as u8) | ch_flag,
-            decimal_to_packed_bcd(datetime.minute() as u8),
-            hour,
-            datetime.weekday().number_from_sunday() as u8,
-            decimal_to_packed_bcd(datetime.day() as u8),
-            decimal_to_packed_bcd(datetime.month() as u8),
-            decimal_to_packed_bcd((datetime.year() - 2000) as u8),
+            decimal_to_packed_bcd(datetime.second) | ch_flag,
+            decimal_to_packed_bcd(datetime.minute),
+            decimal_to_packed_bcd(datetime.hour),
+            datetime.weekday,
+            decimal_to_packed_bcd(datetime.day),
+            decimal_to_packed_bcd(datetime.month),
+            decimal_to_packed_bcd((datetime.year - 2000) as u8),
         ];
-        self.i2c.write(ADDR, &payload).map_err(Error::I2C)
+        self.write_registers(&payload)
     }
 }
 
+#[cfg(feature = "chrono")]
+impl RawDateTime {
+    pub(crate) fn to_naive_datetime(self) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(self.year.into(), self.month.into(), self.day.into())?.and_hms_opt(
+            self.hour.into(),
+            self.minute.into(),
+            self.second.into(),
+        )
+    }
+
+    /// Returns `None` if the year is out of the supported range.
+    pub(crate) fn from_naive_datetime(
+        datetime: &NaiveDateTime,
+        convention: WeekdayConvention,
+    ) -> Option<Self> {
+        if datetime.year() < 2000 || datetime.year() > 2099 {
+            return None;
+        }
+        Some(RawDateTime {
+            year: datetime.year() as u16,
+            month: datetime.month() as u8,
+            day: datetime.day() as u8,
+            weekday: convention.register_value(datetime.weekday().num_days_from_sunday() as u8),
+            hour: datetime.hour() as u8,
+            minute: datetime.minute() as u8,
+            second: datetime.second() as u8,
+        })
+    }
+}
+
+/// Seconds elapsed since 2000-01-01 00:00:00, the earliest date supported by the device.
+///
+/// Returns `None` if the date and time cannot be represented.
+#[cfg(feature = "chrono")]
+pub(crate) fn to_rtc_epoch(datetime: &NaiveDateTime) -> Option<u32> {
+    let seconds = datetime.signed_duration_since(rtc_epoch()).num_seconds();
+    u32::try_from(seconds).ok()
+}
+
+/// Date and time from the seconds elapsed since 2000-01-01 00:00:00.
+#[cfg(feature = "chrono")]
+pub(crate) fn from_rtc_epoch(seconds: u32) -> Option<NaiveDateTime> {
+    rtc_epoch().checked_add_signed(chrono::TimeDelta::seconds(seconds.into()))
+}
+
+#[cfg(feature = "chrono")]
+fn rtc_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "chrono")]
+impl<I2C, E> DateTimeAccess for Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = Error<E>;
+
+    fn datetime(&mut self) -> Result<NaiveDateTime, Self::Error> {
+        self.read_raw_datetime()?
+            .to_naive_datetime()
+            .ok_or(Error::InvalidInputData)
+    }
+
+    fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Self::Error> {
+        let raw = RawDateTime::from_naive_datetime(datetime, self.weekday_convention)
+            .ok_or(Error::InvalidInputData)?;
+        self.write_raw_datetime(&raw)
+    }
+}
+
+#[cfg(feature = "chrono")]
 #[allow(clippy::manual_range_contains)]
 impl<I2C, E> Rtcc for Ds1307<I2C>
 where
@@ -85,9 +200,7 @@ where
 
     fn date(&mut self) -> Result<NaiveDate, Self::Error> {
         let mut data = [0; 3];
-        self.i2c
-            .write_read(ADDR, &[Register::DOM], &mut data)
-            .map_err(Error::I2C)?;
+        self.read_registers(Register::DOM, &mut data)?;
         let year = 2000 + u16::from(packed_bcd_to_decimal(data[2]));
         let month = packed_bcd_to_decimal(data[1]);
         let day = packed_bcd_to_decimal(data[0]);
@@ -97,13 +210,11 @@ where
 
     fn time(&mut self) -> Result<NaiveTime, Self::Error> {
         let mut data = [0; 3];
-        self.i2c
-            .write_read(ADDR, &[Register::SECONDS], &mut data)
-            .map_err(Error::I2C)?;
-        let hour = self.get_hours_from_register(data[Register::HOURS as usize])?;
+        self.read_registers(Register::SECONDS, &mut data)?;
+        let hour = hours_register_to_h24(data[Register::HOURS as usize]);
         let minute = packed_bcd_to_decimal(data[Register::MINUTES as usize]);
         let second = packed_bcd_to_decimal(remove_ch_bit(data[Register::SECONDS as usize]));
-        NaiveTime::from_hms_opt(get_h24(hour).into(), minute.into(), second.into())
+        NaiveTime::from_hms_opt(hour.into(), minute.into(), second.into())
             .ok_or(Error::InvalidInputData)
     }
 
@@ -165,12 +276,12 @@ where
         }
         let payload = [
             Register::DOW,
-            date.weekday().number_from_sunday() as u8,
+            self.weekday_register_value(date),
             decimal_to_packed_bcd(date.day() as u8),
             decimal_to_packed_bcd(date.month() as u8),
             decimal_to_packed_bcd((date.year() - 2000) as u8),
         ];
-        self.i2c.write(ADDR, &payload).map_err(Error::I2C)
+        self.write_registers(&payload)
     }
 
     fn set_time(&mut self, time: &NaiveTime) -> Result<(), Self::Error> {
@@ -182,10 +293,11 @@ where
             decimal_to_packed_bcd(time.minute() as u8),
             hour,
         ];
-        self.i2c.write(ADDR, &payload).map_err(Error::I2C)
+        self.write_registers(&payload)
     }
 }
 
+#[cfg(feature = "chrono")]
 #[allow(clippy::manual_range_contains)]
 impl<I2C, E> Ds1307<I2C>
 where
@@ -205,7 +317,7 @@ where
         }
     }
 
-    fn get_hours_register_value(&mut self, hours: Hours) -> Result<u8, Error<E>> {
+    pub(crate) fn get_hours_register_value(&mut self, hours: Hours) -> Result<u8, Error<E>> {
         match hours {
             Hours::H24(h) if h > 23 => Err(Error::InvalidInputData),
             Hours::H24(h) => Ok(decimal_to_packed_bcd(h)),
@@ -226,78 +338,22 @@ where
     }
 }
 
-fn is_24h_format(hours_data: u8) -> bool {
-    hours_data & BitFlags::H24_H12 == 0
-}
-
-fn is_am(hours_data: u8) -> bool {
-    hours_data & BitFlags::AM_PM == 0
-}
-
-fn remove_ch_bit(value: u8) -> u8 {
-    value & !BitFlags::CH
-}
-
-/// Transforms a number in packed BCD format to decimal
-fn packed_bcd_to_decimal(bcd: u8) -> u8 {
-    (bcd >> 4) * 10 + (bcd & 0xF)
-}
-
-/// Transforms a decimal number to packed BCD format
-fn decimal_to_packed_bcd(dec: u8) -> u8 {
-    ((dec / 10) << 4) | (dec % 10)
-}
-
-fn get_h24(hour: Hours) -> u8 {
-    match hour {
-        Hours::H24(h) => h,
-        Hours::AM(h) => h,
-        Hours::PM(h) => h + 12,
-    }
-}
-
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use super::*;
 
     #[test]
-    fn can_convert_to_h24() {
-        assert_eq!(0, get_h24(Hours::H24(0)));
-        assert_eq!(0, get_h24(Hours::AM(0)));
-        assert_eq!(12, get_h24(Hours::PM(0)));
-
-        assert_eq!(1, get_h24(Hours::H24(1)));
-        assert_eq!(1, get_h24(Hours::AM(1)));
-        assert_eq!(13, get_h24(Hours::PM(1)));
-
-        assert_eq!(23, get_h24(Hours::H24(23)));
-        assert_eq!(12, get_h24(Hours::AM(12)));
-        assert_eq!(23, get_h24(Hours::PM(11)));
-    }
-
-    #[test]
-    fn can_convert_packed_bcd_to_decimal() {
-        assert_eq!(0, packed_bcd_to_decimal(0b0000_0000));
-        assert_eq!(1, packed_bcd_to_decimal(0b0000_0001));
-        assert_eq!(9, packed_bcd_to_decimal(0b0000_1001));
-        assert_eq!(10, packed_bcd_to_decimal(0b0001_0000));
-        assert_eq!(11, packed_bcd_to_decimal(0b0001_0001));
-        assert_eq!(19, packed_bcd_to_decimal(0b0001_1001));
-        assert_eq!(20, packed_bcd_to_decimal(0b0010_0000));
-        assert_eq!(21, packed_bcd_to_decimal(0b0010_0001));
-        assert_eq!(59, packed_bcd_to_decimal(0b0101_1001));
-    }
-
-    #[test]
-    fn can_convert_decimal_to_packed_bcd() {
-        assert_eq!(0b0000_0000, decimal_to_packed_bcd(0));
-        assert_eq!(0b0000_0001, decimal_to_packed_bcd(1));
-        assert_eq!(0b0000_1001, decimal_to_packed_bcd(9));
-        assert_eq!(0b0001_0000, decimal_to_packed_bcd(10));
-        assert_eq!(0b0001_0001, decimal_to_packed_bcd(11));
-        assert_eq!(0b0001_1001, decimal_to_packed_bcd(19));
-        assert_eq!(0b0010_0000, decimal_to_packed_bcd(20));
-        assert_eq!(0b0010_0001, decimal_to_packed_bcd(21));
-        assert_eq!(0b0101_1001, decimal_to_packed_bcd(59));
+    fn can_convert_rtc_epoch() {
+        let datetime = NaiveDate::from_ymd_opt(2018, 8, 13)
+            .unwrap()
+            .and_hms_opt(23, 59, 58)
+            .unwrap();
+        assert_eq!(Some(0), to_rtc_epoch(&rtc_epoch()));
+        assert_eq!(Some(587_519_998), to_rtc_epoch(&datetime));
+        assert_eq!(Some(datetime), from_rtc_epoch(587_519_998));
+        assert_eq!(
+            None,
+            to_rtc_epoch(&(rtc_epoch() - chrono::TimeDelta::seconds(1)))
+        );
     }
 }
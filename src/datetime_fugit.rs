@@ -0,0 +1,108 @@
+use crate::datetime::RawDateTime;
+use crate::{BitFlags, DateTimeAccess, Ds1307, Error, NaiveDateTime, Register};
+use chrono::DateTime;
+use embedded_hal::i2c::I2c;
+
+/// Instant in seconds since the Unix epoch (1970-01-01 00:00:00).
+pub type EpochInstant = fugit::Instant<u64, 1, 1>;
+
+/// Duration in seconds.
+pub type EpochDuration = fugit::Duration<u64, 1, 1>;
+
+/// Convert a date and time into an instant since the Unix epoch.
+///
+/// Returns `None` if the date and time lies before the epoch.
+pub fn datetime_to_instant(datetime: &NaiveDateTime) -> Option<EpochInstant> {
+    let seconds = u64::try_from(datetime.and_utc().timestamp()).ok()?;
+    Some(EpochInstant::from_ticks(seconds))
+}
+
+/// Convert an instant since the Unix epoch into a date and time.
+///
+/// Returns `None` if the instant cannot be represented as a date and time.
+pub fn instant_to_datetime(instant: EpochInstant) -> Option<NaiveDateTime> {
+    let seconds = i64::try_from(instant.ticks()).ok()?;
+    DateTime::from_timestamp(seconds, 0).map(|datetime| datetime.naive_utc())
+}
+
+/// Compute the duration between two date and time values.
+///
+/// Returns `None` if `later` lies before `earlier`.
+pub fn duration_between(earlier: &NaiveDateTime, later: &NaiveDateTime) -> Option<EpochDuration> {
+    let seconds = u64::try_from(later.signed_duration_since(*earlier).num_seconds()).ok()?;
+    Some(EpochDuration::from_ticks(seconds))
+}
+
+/// Error computing the time elapsed between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElapsedError {
+    /// The clock was halted in at least one of the snapshots so the time
+    /// difference does not reflect the time elapsed.
+    Halted,
+    /// The later snapshot lies before the earlier one.
+    Backwards,
+}
+
+/// Date and time read from the device together with the clock running status
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapshot {
+    datetime: NaiveDateTime,
+    running: bool,
+}
+
+impl Snapshot {
+    /// Date and time at the moment of the snapshot.
+    pub fn datetime(&self) -> NaiveDateTime {
+        self.datetime
+    }
+
+    /// Whether the clock was running at the moment of the snapshot.
+    pub fn running(&self) -> bool {
+        self.running
+    }
+
+    /// Instant since the Unix epoch at the moment of the snapshot.
+    pub fn instant(&self) -> EpochInstant {
+        // The device only supports years 2000-2099.
+        datetime_to_instant(&self.datetime).unwrap_or(EpochInstant::from_ticks(0))
+    }
+
+    /// Time elapsed between an earlier snapshot and this one.
+    ///
+    /// Will return `ElapsedError::Halted` if the clock was halted in any of
+    /// the snapshots and `ElapsedError::Backwards` if this snapshot lies
+    /// before `earlier`.
+    pub fn duration_since(&self, earlier: &Snapshot) -> Result<EpochDuration, ElapsedError> {
+        if !self.running || !earlier.running {
+            return Err(ElapsedError::Halted);
+        }
+        duration_between(&earlier.datetime, &self.datetime).ok_or(ElapsedError::Backwards)
+    }
+}
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the date and time together with the clock running status at once.
+    ///
+    /// Requires the `fugit` feature.
+    pub fn snapshot(&mut self) -> Result<Snapshot, Error<E>> {
+        let data = self.read_datetime_registers()?;
+        let datetime = RawDateTime::from_registers(&data)
+            .to_naive_datetime()
+            .ok_or(Error::InvalidInputData)?;
+        Ok(Snapshot {
+            datetime,
+            running: data[Register::SECONDS as usize] & BitFlags::CH == 0,
+        })
+    }
+
+    /// Read the date and time as an instant since the Unix epoch.
+    ///
+    /// Requires the `fugit` feature.
+    pub fn instant(&mut self) -> Result<EpochInstant, Error<E>> {
+        let datetime = self.datetime()?;
+        datetime_to_instant(&datetime).ok_or(Error::InvalidInputData)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::datetime::RawDateTime;
+use crate::{Ds1307, Error};
+use embedded_hal::i2c::I2c;
+use jiff::civil::DateTime;
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the date and time as a [`jiff::civil::DateTime`].
+    ///
+    /// Requires the `jiff` feature.
+    pub fn civil_datetime(&mut self) -> Result<DateTime, Error<E>> {
+        let raw = self.read_raw_datetime()?;
+        DateTime::new(
+            raw.year as i16,
+            raw.month as i8,
+            raw.day as i8,
+            raw.hour as i8,
+            raw.minute as i8,
+            raw.second as i8,
+            0,
+        )
+        .map_err(|_| Error::InvalidInputData)
+    }
+
+    /// Set the date and time from a [`jiff::civil::DateTime`].
+    ///
+    /// Will return an `Error::InvalidInputData` if the year is not in the range [2000-2099].
+    ///
+    /// Requires the `jiff` feature.
+    pub fn set_civil_datetime(&mut self, datetime: &DateTime) -> Result<(), Error<E>> {
+        let year = u16::try_from(datetime.year()).map_err(|_| Error::InvalidInputData)?;
+        self.write_raw_datetime(&RawDateTime {
+            year,
+            month: datetime.month() as u8,
+            day: datetime.day() as u8,
+            weekday: self
+                .weekday_convention
+                .register_value(datetime.weekday().to_sunday_zero_offset() as u8),
+            hour: datetime.hour() as u8,
+            minute: datetime.minute() as u8,
+            second: datetime.second() as u8,
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use crate::datetime::RawDateTime;
+use crate::{Ds1307, Error};
+use embedded_hal::i2c::I2c;
+use time::{Date, Month, PrimitiveDateTime, Time};
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the date and time as a [`time::PrimitiveDateTime`].
+    ///
+    /// Requires the `time` feature.
+    pub fn primitive_datetime(&mut self) -> Result<PrimitiveDateTime, Error<E>> {
+        let raw = self.read_raw_datetime()?;
+        let month = Month::try_from(raw.month).map_err(|_| Error::InvalidInputData)?;
+        let date = Date::from_calendar_date(raw.year.into(), month, raw.day)
+            .map_err(|_| Error::InvalidInputData)?;
+        let time = Time::from_hms(raw.hour, raw.minute, raw.second)
+            .map_err(|_| Error::InvalidInputData)?;
+        Ok(PrimitiveDateTime::new(date, time))
+    }
+
+    /// Set the date and time from a [`time::PrimitiveDateTime`].
+    ///
+    /// Will return an `Error::InvalidInputData` if the year is not in the range [2000-2099].
+    ///
+    /// Requires the `time` feature.
+    pub fn set_primitive_datetime(&mut self, datetime: &PrimitiveDateTime) -> Result<(), Error<E>> {
+        let year = u16::try_from(datetime.year()).map_err(|_| Error::InvalidInputData)?;
+        self.write_raw_datetime(&RawDateTime {
+            year,
+            month: datetime.month().into(),
+            day: datetime.day(),
+            weekday: self
+                .weekday_convention
+                .register_value(datetime.weekday().number_days_from_sunday()),
+            hour: datetime.hour(),
+            minute: datetime.minute(),
+            second: datetime.second(),
+        })
+    }
+}
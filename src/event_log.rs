@@ -0,0 +1,250 @@
+use crate::crc::crc8;
+use crate::datetime::{from_rtc_epoch, to_rtc_epoch};
+use crate::ram::RAM_BYTE_COUNT;
+use crate::ram_record::read_u32;
+use crate::{DateTimeAccess, Ds1307, Error, NaiveDateTime};
+use embedded_hal::i2c::I2c;
+
+const HEADER_SIZE: u8 = 4;
+const ENTRY_SIZE: u8 = 5;
+
+/// Event stored in an [`EventLog`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Event {
+    /// Application-defined event code.
+    pub code: u8,
+    /// Date and time when the event was recorded.
+    pub timestamp: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Header {
+    sequence: u8,
+    head: u8,
+    count: u8,
+}
+
+impl Header {
+    fn decode(data: &[u8]) -> Option<Self> {
+        if crc8(&data[..3]) != data[3] {
+            return None;
+        }
+        Some(Header {
+            sequence: data[0],
+            head: data[1],
+            count: data[2],
+        })
+    }
+
+    fn encode(&self) -> [u8; HEADER_SIZE as usize] {
+        let data = [self.sequence, self.head, self.count];
+        [data[0], data[1], data[2], crc8(&data)]
+    }
+}
+
+/// Ring buffer of timestamped events stored in the user RAM.
+///
+/// Each event takes 5 bytes: a 4-byte timestamp in seconds since
+/// 2000-01-01 00:00:00 and a 1-byte event code. When the log is full, the
+/// oldest event is overwritten.
+///
+/// The log metadata is stored twice with a sequence number and a CRC. Updates
+/// alternate between both copies and a spare entry slot is kept so that an
+/// interrupted write never corrupts the events already stored.
+/// Using the whole user RAM, up to 8 events can be stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventLog {
+    address_offset: u8,
+    slots: u8,
+    header: Header,
+    header_slot: u8,
+}
+
+impl EventLog {
+    /// Open the event log stored in the user RAM region starting at
+    /// `address_offset` with `length` bytes.
+    ///
+    /// If no valid log is found in the region, an empty log is used.
+    ///
+    /// Will return an `Error::InvalidInputData` if the region does not fit in
+    /// the user RAM or is too small to hold at least one event.
+    pub fn open<I2C, E>(
+        rtc: &mut Ds1307<I2C>,
+        address_offset: u8,
+        length: u8,
+    ) -> Result<Self, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        if length < 2 * HEADER_SIZE + 2 * ENTRY_SIZE
+            || usize::from(address_offset) + usize::from(length) > RAM_BYTE_COUNT
+        {
+            return Err(Error::InvalidInputData);
+        }
+        let slots = (length - 2 * HEADER_SIZE) / ENTRY_SIZE;
+        let mut data = [0; 2 * HEADER_SIZE as usize];
+        rtc.read_ram(address_offset, &mut data)?;
+        let valid = |header: &Header| header.head < slots && header.count < slots;
+        let first = Header::decode(&data[..4]).filter(valid);
+        let second = Header::decode(&data[4..]).filter(valid);
+        let (header, header_slot) = match (first, second) {
+            (Some(a), Some(b)) if (b.sequence.wrapping_sub(a.sequence) as i8) > 0 => (b, 1),
+            (Some(a), _) => (a, 0),
+            (None, Some(b)) => (b, 1),
+            (None, None) => (
+                Header {
+                    sequence: 0,
+                    head: 0,
+                    count: 0,
+                },
+                1,
+            ),
+        };
+        Ok(EventLog {
+            address_offset,
+            slots,
+            header,
+            header_slot,
+        })
+    }
+
+    /// Maximum number of events that can be stored.
+    pub fn capacity(&self) -> usize {
+        usize::from(self.slots - 1)
+    }
+
+    /// Number of events stored.
+    pub fn len(&self) -> usize {
+        usize::from(self.header.count)
+    }
+
+    /// Whether there are no events stored.
+    pub fn is_empty(&self) -> bool {
+        self.header.count == 0
+    }
+
+    /// Record an event with the current date and time.
+    pub fn push<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>, code: u8) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let now = rtc.datetime()?;
+        self.push_at(rtc, code, &now)
+    }
+
+    /// Record an event with the given date and time.
+    ///
+    /// Will return an `Error::InvalidInputData` if the date and time is out
+    /// of the range supported by the device.
+    pub fn push_at<I2C, E>(
+        &mut self,
+        rtc: &mut Ds1307<I2C>,
+        code: u8,
+        timestamp: &NaiveDateTime,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let seconds = to_rtc_epoch(timestamp).ok_or(Error::InvalidInputData)?;
+        // The slot after the last event is always free so the stored events
+        // stay intact if this write is interrupted.
+        let slot = (self.header.head + self.header.count) % self.slots;
+        let mut entry = [0; ENTRY_SIZE as usize];
+        entry[..4].copy_from_slice(&seconds.to_le_bytes());
+        entry[4] = code;
+        rtc.write_ram(self.entry_address(slot), &entry)?;
+        let mut header = self.header;
+        if usize::from(header.count) == self.capacity() {
+            header.head = (header.head + 1) % self.slots;
+        } else {
+            header.count += 1;
+        }
+        self.store_header(rtc, header)
+    }
+
+    /// Remove all events.
+    pub fn clear<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let header = Header {
+            count: 0,
+            ..self.header
+        };
+        self.store_header(rtc, header)
+    }
+
+    /// Read the stored events, oldest first.
+    pub fn iter<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<EventIter, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let mut data = [0; RAM_BYTE_COUNT];
+        let length = usize::from(self.slots * ENTRY_SIZE);
+        rtc.read_ram(self.entry_address(0), &mut data[..length])?;
+        Ok(EventIter {
+            data,
+            slots: self.slots,
+            next: self.header.head,
+            remaining: self.header.count,
+        })
+    }
+
+    fn entry_address(&self, slot: u8) -> u8 {
+        self.address_offset + 2 * HEADER_SIZE + slot * ENTRY_SIZE
+    }
+
+    fn store_header<I2C, E>(
+        &mut self,
+        rtc: &mut Ds1307<I2C>,
+        header: Header,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let header = Header {
+            sequence: self.header.sequence.wrapping_add(1),
+            ..header
+        };
+        let header_slot = 1 - self.header_slot;
+        rtc.write_ram(
+            self.address_offset + header_slot * HEADER_SIZE,
+            &header.encode(),
+        )?;
+        self.header = header;
+        self.header_slot = header_slot;
+        Ok(())
+    }
+}
+
+/// Iterator over the events stored in an [`EventLog`], oldest first
+#[derive(Debug, Clone)]
+pub struct EventIter {
+    data: [u8; RAM_BYTE_COUNT],
+    slots: u8,
+    next: u8,
+    remaining: u8,
+}
+
+impl Iterator for EventIter {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let start = usize::from(self.next * ENTRY_SIZE);
+        let entry = &self.data[start..start + usize::from(ENTRY_SIZE)];
+        self.next = (self.next + 1) % self.slots;
+        self.remaining -= 1;
+        let seconds = read_u32(entry);
+        Some(Event {
+            code: entry[4],
+            timestamp: from_rtc_epoch(seconds)?,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(usize::from(self.remaining)))
+    }
+}
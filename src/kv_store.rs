@@ -0,0 +1,238 @@
+use crate::crc::crc8;
+use crate::ram::RAM_BYTE_COUNT;
+use crate::{Ds1307, Error};
+use embedded_hal::i2c::I2c;
+
+const HEADER_SIZE: usize = 2;
+const RECORD_HEADER_SIZE: usize = 2;
+const DELETED: u8 = 0xFF;
+
+/// Tiny key-value store in the user RAM.
+///
+/// Values are stored as type-length-value records after a 2-byte header
+/// containing a CRC and the used length. Each record takes 2 bytes plus the
+/// length of the value. Keys can be in the range [0-254].
+///
+/// Removed records are only marked as deleted. Their space is reclaimed
+/// by [`compact()`](KvStore::compact), which is also done automatically
+/// when setting a value does not fit otherwise.
+///
+/// The contents are cached so reading values does not access the device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KvStore {
+    address_offset: u8,
+    length: u8,
+    data: [u8; RAM_BYTE_COUNT],
+}
+
+impl KvStore {
+    /// Load the store from the user RAM region starting at `address_offset`
+    /// with `length` bytes.
+    ///
+    /// Will return an `Error::CorruptedData` if the region does not contain
+    /// a valid store, for example on first use. In this case a new store
+    /// can be created with [`format()`](KvStore::format).
+    ///
+    /// Will return an `Error::InvalidInputData` if the region does not fit in
+    /// the user RAM or is too small to hold any record.
+    pub fn load<I2C, E>(
+        rtc: &mut Ds1307<I2C>,
+        address_offset: u8,
+        length: u8,
+    ) -> Result<Self, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let mut store = Self::new(address_offset, length)?;
+        rtc.read_ram(address_offset, &mut store.data[..usize::from(length)])?;
+        let used = store.used();
+        if HEADER_SIZE + used > usize::from(length) || store.crc() != store.data[0] {
+            return Err(Error::CorruptedData);
+        }
+        let mut position = HEADER_SIZE;
+        while position < HEADER_SIZE + used {
+            position +=
+                RECORD_HEADER_SIZE + usize::from(*store.data.get(position + 1).unwrap_or(&0));
+        }
+        if position != HEADER_SIZE + used {
+            return Err(Error::CorruptedData);
+        }
+        Ok(store)
+    }
+
+    /// Create an empty store in the user RAM region starting at
+    /// `address_offset` with `length` bytes, discarding its previous contents.
+    ///
+    /// Will return an `Error::InvalidInputData` if the region does not fit in
+    /// the user RAM or is too small to hold any record.
+    pub fn format<I2C, E>(
+        rtc: &mut Ds1307<I2C>,
+        address_offset: u8,
+        length: u8,
+    ) -> Result<Self, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let mut store = Self::new(address_offset, length)?;
+        store.store(rtc)?;
+        Ok(store)
+    }
+
+    /// Get the value stored for a key.
+    pub fn get(&self, key: u8) -> Option<&[u8]> {
+        self.find(key).map(|position| self.value(position))
+    }
+
+    /// Set the value for a key.
+    ///
+    /// Will return an `Error::OutOfSpace` if the value does not fit in the
+    /// free space, in which case the store is not modified.
+    ///
+    /// Will return an `Error::InvalidInputData` if the key is 255.
+    pub fn set<I2C, E>(
+        &mut self,
+        rtc: &mut Ds1307<I2C>,
+        key: u8,
+        value: &[u8],
+    ) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        if key == DELETED {
+            return Err(Error::InvalidInputData);
+        }
+        let existing = self.find(key);
+        if let Some(position) = existing {
+            if self.value(position).len() == value.len() {
+                let start = position + RECORD_HEADER_SIZE;
+                self.data[start..start + value.len()].copy_from_slice(value);
+                return self.store(rtc);
+            }
+        }
+        let existing_size = existing.map_or(0, |position| self.record_size(position));
+        let needed = RECORD_HEADER_SIZE + value.len();
+        if needed > self.free() + self.deleted() + existing_size {
+            return Err(Error::OutOfSpace);
+        }
+        if let Some(position) = existing {
+            self.data[position] = DELETED;
+        }
+        if needed > self.free() {
+            self.remove_deleted();
+        }
+        let start = HEADER_SIZE + self.used();
+        self.data[start] = key;
+        self.data[start + 1] = value.len() as u8;
+        self.data[start + RECORD_HEADER_SIZE..start + needed].copy_from_slice(value);
+        self.data[1] += needed as u8;
+        self.store(rtc)
+    }
+
+    /// Remove the value for a key.
+    ///
+    /// Returns whether the key was present.
+    pub fn remove<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>, key: u8) -> Result<bool, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        match self.find(key) {
+            Some(position) => {
+                self.data[position] = DELETED;
+                self.store(rtc)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Reclaim the space of removed records.
+    pub fn compact<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        self.remove_deleted();
+        self.store(rtc)
+    }
+
+    /// Number of bytes available for new records without compaction.
+    pub fn free(&self) -> usize {
+        usize::from(self.length) - HEADER_SIZE - self.used()
+    }
+
+    fn new<E>(address_offset: u8, length: u8) -> Result<Self, Error<E>> {
+        if usize::from(length) < HEADER_SIZE + RECORD_HEADER_SIZE + 1
+            || usize::from(address_offset) + usize::from(length) > RAM_BYTE_COUNT
+        {
+            return Err(Error::InvalidInputData);
+        }
+        Ok(KvStore {
+            address_offset,
+            length,
+            data: [0; RAM_BYTE_COUNT],
+        })
+    }
+
+    fn used(&self) -> usize {
+        usize::from(self.data[1])
+    }
+
+    fn deleted(&self) -> usize {
+        self.records()
+            .filter(|position| self.data[*position] == DELETED)
+            .map(|position| self.record_size(position))
+            .sum()
+    }
+
+    fn records(&self) -> impl Iterator<Item = usize> + '_ {
+        let end = HEADER_SIZE + self.used();
+        let mut position = HEADER_SIZE;
+        core::iter::from_fn(move || {
+            if position >= end {
+                return None;
+            }
+            let current = position;
+            position += self.record_size(current);
+            Some(current)
+        })
+    }
+
+    fn find(&self, key: u8) -> Option<usize> {
+        self.records().find(|position| self.data[*position] == key)
+    }
+
+    fn record_size(&self, position: usize) -> usize {
+        RECORD_HEADER_SIZE + usize::from(self.data[position + 1])
+    }
+
+    fn value(&self, position: usize) -> &[u8] {
+        let start = position + RECORD_HEADER_SIZE;
+        &self.data[start..start + usize::from(self.data[position + 1])]
+    }
+
+    fn remove_deleted(&mut self) {
+        let end = HEADER_SIZE + self.used();
+        let mut read = HEADER_SIZE;
+        let mut write = HEADER_SIZE;
+        while read < end {
+            let size = self.record_size(read);
+            if self.data[read] != DELETED {
+                self.data.copy_within(read..read + size, write);
+                write += size;
+            }
+            read += size;
+        }
+        self.data[1] = (write - HEADER_SIZE) as u8;
+    }
+
+    fn crc(&self) -> u8 {
+        crc8(&self.data[1..HEADER_SIZE + self.used()])
+    }
+
+    fn store<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        self.data[0] = self.crc();
+        rtc.write_ram(self.address_offset, &self.data[..HEADER_SIZE + self.used()])
+    }
+}
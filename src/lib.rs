@@ -3,14 +3,42 @@
 //!
 //! This driver allows you to:
 //! - Read and set date and time in 12-hour and 24-hour format. See: [`datetime()`].
+//! - Read and set date and time as [`time`] or [`jiff`] types. See: `primitive_datetime()`
+//!   and `civil_datetime()`.
 //! - Enable and disable the real-time clock. See: [`set_running()`].
 //! - Read and write user RAM. See: [`read_ram()`].
+//! - Estimate the health of the backup battery. See: `BackupMonitor`.
+//! - Keep a persistent boot counter and uptime ledger in user RAM. See: `BootLedger`.
+//! - Log timestamped events in user RAM. See: `EventLog`.
+//! - Store small values by key in user RAM. See: [`KvStore`].
+//! - Declare user RAM layouts checked at compile time. See: [`ram_layout!`].
+//! - Synchronize the RTC and the system clock of a host. See: `ClockSync`.
+//! - Evaluate cron schedules against the RTC time. See: `Scheduler`.
+//! - Detect the clock going backwards or jumping forward. See: `MonotonicGuard`.
 //! - Control square-wave output. See: [`enable_square_wave_output()`].
+//! - Read and write the whole control register at once. See: [`configure()`].
+//! - Use the SQW/OUT pin as a generic digital output. See: [`SqwOutPin`].
+//! - Measure the error of a clock against the square-wave output. See: [`Calibration`].
+//! - Execute text commands from a serial console or shell. See: `Console`.
+//! - Parse and format dates and times as text without allocation. See: [`TimeInput`].
+//! - Nudge the time forward or backward preserving the clock state. See: `adjust()`.
+//! - Get day of the year, ISO week and month boundaries from one reading. See: `calendar()`.
+//! - Check and repair the day of the week. See: `weekday_consistent()`.
+//! - Share the I²C bus with other devices. See: [`Ds1307Ref`].
+//! - Retry failed I²C transfers with backoff and bus recovery. See: [`RetryI2c`].
+//! - Verify register writes by reading them back. See: [`set_write_verification()`].
+//! - Serve wall-clock time from the Embassy timer (`embassy-time` feature). See: `WallClock`.
+//! - Calculate sunrise, sunset and civil twilight (`solar` feature). See: `Location`.
+//! - Share the time vocabulary with `fugit`-based schedulers (`fugit` feature). See: `snapshot()`.
 //!
 //! [`datetime()`]: Ds1307::datetime
+//! [`time`]: https://crates.io/crates/time
+//! [`jiff`]: https://crates.io/crates/jiff
 //! [`set_running()`]: Ds1307::set_running
 //! [`read_ram()`]: Ds1307::read_ram
 //! [`enable_square_wave_output()`]: Ds1307::enable_square_wave_output
+//! [`configure()`]: Ds1307::configure
+//! [`set_write_verification()`]: Ds1307::set_write_verification
 //!
 //! ## The device
 //!
@@ -29,6 +57,27 @@
 //!
 //! Datasheet: [DS1307](https://datasheets.maximintegrated.com/en/ds/DS1307.pdf)
 //!
+//! ## Features
+//!
+//! - `chrono` (enabled by default): Date and time access through the [`rtcc`]
+//!   traits using [`chrono`] types.
+//! - `time`: Date and time access using [`time`] types.
+//! - `jiff`: Date and time access using [`jiff`] types.
+//! - `embassy-time`: Wall-clock time source based on the Embassy timer.
+//! - `fugit`: Conversion to [`fugit`] instants and durations.
+//! - `solar`: Sunrise, sunset and civil twilight calculations using [`libm`].
+//! - `testing`: Expectation builders for testing application code with
+//!   [`embedded-hal-mock`]. See the `testing` module.
+//!
+//! The core driver (clock halt, user RAM and square-wave output) can be used
+//! with `default-features = false`.
+//!
+//! [`rtcc`]: https://crates.io/crates/rtcc
+//! [`chrono`]: https://crates.io/crates/chrono
+//! [`fugit`]: https://crates.io/crates/fugit
+//! [`embedded-hal-mock`]: https://crates.io/crates/embedded-hal-mock
+//! [`libm`]: https://crates.io/crates/libm
+//!
 //! ## Usage examples (see also examples folder)
 //!
 //! Please find additional examples using hardware in this repository: [driver-examples]
@@ -37,11 +86,15 @@
 //!
 //! ### Set and get the current date and time all at once
 //!
+//! Requires the `chrono` feature (enabled by default).
+//!
 //! Calling the `datetime`/ `set_datetime` methods is recommended
 //! to avoid inconsistencies when reading date/time parts and combining them
 //! as time passes.
 //!
 //! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
 //! use linux_embedded_hal as hal;
 //! use ds1307::{Ds1307, NaiveDate, DateTimeAccess};
 //!
@@ -53,6 +106,53 @@
 //! let datetime = rtc.datetime().unwrap();
 //! println!("{}", datetime);
 //! // This will print something like: 2020-05-02 19:59:58
+//! # }
+//! ```
+//!
+//! ### Set and get the date and time using the `time` crate
+//!
+//! Requires the `time` feature. Similar methods exist for the `jiff` crate
+//! with the `jiff` feature: `civil_datetime()` and `set_civil_datetime()`.
+//!
+//! ```no_run
+//! # #[cfg(feature = "time")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::Ds1307;
+//! use time::{Date, Month};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! let datetime = Date::from_calendar_date(2020, Month::May, 2)
+//!     .unwrap()
+//!     .with_hms(19, 59, 58)
+//!     .unwrap();
+//! rtc.set_primitive_datetime(&datetime).unwrap();
+//! // ...
+//! let datetime = rtc.primitive_datetime().unwrap();
+//! # }
+//! ```
+//!
+//! ### Measure elapsed time with `fugit` types
+//!
+//! Requires the `fugit` feature. The clock running status is read together
+//! with the date and time so that the elapsed time is not reported if the
+//! clock was halted.
+//!
+//! ```no_run
+//! # #[cfg(feature = "fugit")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::Ds1307;
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! let start = rtc.snapshot().unwrap();
+//! // ...
+//! let end = rtc.snapshot().unwrap();
+//! let elapsed = end.duration_since(&start).unwrap();
+//! println!("Elapsed: {} s, since epoch: {}", elapsed.to_secs(), end.instant());
+//! # }
 //! ```
 //!
 //! ### Get the year
@@ -64,6 +164,8 @@
 //! as time passes.
 //!
 //! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
 //! use linux_embedded_hal as hal;
 //! use ds1307::{Ds1307, Rtcc};
 //!
@@ -71,6 +173,7 @@
 //! let mut rtc = Ds1307::new(dev);
 //! let year = rtc.year().unwrap();
 //! println!("Year: {}", year);
+//! # }
 //! ```
 //!
 //! ### Set the year
@@ -82,12 +185,15 @@
 //! as time passes.
 //!
 //! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
 //! use linux_embedded_hal as hal;
 //! use ds1307::{Ds1307, Rtcc};
 //!
 //! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
 //! let mut rtc = Ds1307::new(dev);
 //! rtc.set_year(2018).unwrap();
+//! # }
 //! ```
 //!
 //! ### Get the current date
@@ -96,6 +202,8 @@
 //! getting the time.
 //!
 //! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
 //! use linux_embedded_hal as hal;
 //! use ds1307::{Ds1307, Rtcc};
 //!
@@ -103,6 +211,83 @@
 //! let mut rtc = Ds1307::new(dev);
 //! let date = rtc.date().unwrap();
 //! println!("{}", date);
+//! # }
+//! ```
+//!
+//! ### Set and print the time from a serial console
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{format_datetime, DateTimeAccess, Ds1307, TimeInput};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! for command in ["2024-02-01 13:45:00", "+90s", "1:45pm"] {
+//!     let input: TimeInput = command.parse().unwrap();
+//!     rtc.set_time_input(&input).unwrap();
+//! }
+//! let mut out = String::new();
+//! format_datetime(&mut out, &rtc.datetime().unwrap(), "%Y-%m-%d %I:%M:%S %p").unwrap();
+//! println!("{out}");
+//! # }
+//! ```
+//!
+//! ### Handle RTC commands in a serial console
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Console, Ds1307};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! let console = Console::with_format("%d.%m.%Y %H:%M:%S");
+//! let mut out = String::new();
+//! for line in ["set 2024-02-01 13:45", "sqw 1", "sqw on", "ram poke 0 0xAB", "ram dump"] {
+//!     if let Err(e) = console.execute(&mut rtc, line, &mut out) {
+//!         out.push_str(&format!("error: {e:?}\n"));
+//!     }
+//! }
+//! print!("{out}");
+//! # }
+//! ```
+//!
+//! ### Get calendar fields from a single reading
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::Ds1307;
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! let calendar = rtc.calendar().unwrap();
+//! println!("Day {} of the year, ISO week {}", calendar.day_of_year(), calendar.iso_week().week());
+//! if calendar.is_last_day_of_month() {
+//!     println!("Billing rollover today");
+//! }
+//! # }
+//! ```
+//!
+//! ### Check and repair the day of the week
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, WeekdayConvention};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! rtc.set_weekday_convention(WeekdayConvention::MondayOne);
+//! if !rtc.weekday_consistent().unwrap() {
+//!     rtc.repair_weekday().unwrap();
+//! }
+//! # }
 //! ```
 //!
 //! ### Read and write user RAM
@@ -124,6 +309,148 @@
 //! // This will print: 171, 171, 171
 //! ```
 //!
+//! ### Check the backup battery on boot
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{BackupMonitor, BackupStatus, Ds1307, TimeDelta};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! let monitor = BackupMonitor::new(0, TimeDelta::minutes(5));
+//! if monitor.backup_status(&mut rtc, None).unwrap() == BackupStatus::BatteryLikelyFailed {
+//!     println!("Replace the backup battery.");
+//! }
+//! // Call periodically while running.
+//! monitor.heartbeat(&mut rtc).unwrap();
+//! # }
+//! ```
+//!
+//! ### Keep a boot counter and uptime ledger in user RAM
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{BootLedger, Ds1307};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//!
+//! let mut ledger = BootLedger::record_boot(&mut rtc, 0).unwrap();
+//! println!("Boot #{}", ledger.boot_count());
+//! if let Some(downtime) = ledger.last_downtime() {
+//!     println!("Powered off for {} s", downtime.num_seconds());
+//! }
+//! // Periodically:
+//! ledger.heartbeat(&mut rtc).unwrap();
+//! # }
+//! ```
+//!
+//! ### Log timestamped events in user RAM
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, EventLog};
+//!
+//! const DOOR_OPENED: u8 = 1;
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//!
+//! // Use 28 bytes of user RAM starting at offset 20.
+//! let mut log = EventLog::open(&mut rtc, 20, 28).unwrap();
+//! log.push(&mut rtc, DOOR_OPENED).unwrap();
+//! for event in log.iter(&mut rtc).unwrap() {
+//!     println!("{}: {}", event.timestamp, event.code);
+//! }
+//! # }
+//! ```
+//!
+//! ### Declare a user RAM layout checked at compile time
+//!
+//! ```no_run
+//! use linux_embedded_hal as hal;
+//! use ds1307::{ram_layout, Ds1307};
+//!
+//! ram_layout! {
+//!     pub Settings: 0, 4;
+//!     pub Counter: 4, 2;
+//! }
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//!
+//! let counter = Counter::new();
+//! let value = u16::from_le_bytes(counter.read(&mut rtc).unwrap());
+//! counter.write(&mut rtc, &(value + 1).to_le_bytes()).unwrap();
+//! ```
+//!
+//! ### Run cron jobs, including those missed while powered off
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{CronSchedule, Ds1307, Scheduler};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! let jobs = [
+//!     "0 3 * * *".parse::<CronSchedule>().unwrap(), // daily at 03:00
+//!     "30 6 * * 1".parse().unwrap(),                // Mondays at 06:30
+//! ];
+//! let mut scheduler = Scheduler::load(&mut rtc, 0, &jobs).unwrap();
+//! for index in scheduler.due_jobs(&mut rtc).unwrap().iter() {
+//!     println!("Running job {index}");
+//! }
+//! # }
+//! ```
+//!
+//! ### Read the time without ever going backwards
+//!
+//! ```no_run
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, GuardError, JumpPolicy, MonotonicGuard, TimeDelta};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! let max_forward = TimeDelta::hours(1);
+//! let mut guard = MonotonicGuard::load(&mut rtc, 0, JumpPolicy::Report, max_forward).unwrap();
+//! match guard.now(&mut rtc) {
+//!     Ok(now) => println!("{now}"),
+//!     Err(GuardError::Jump(jump)) => println!("Time jump: {jump:?}"),
+//!     Err(GuardError::Rtc(e)) => println!("RTC error: {e:?}"),
+//! }
+//! # }
+//! ```
+//!
+//! ### Store small values by key in user RAM
+//!
+//! ```no_run
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, Error, KvStore};
+//!
+//! const BRIGHTNESS: u8 = 1;
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//!
+//! // Use the first 16 bytes of user RAM.
+//! let mut store = match KvStore::load(&mut rtc, 0, 16) {
+//!     Err(Error::CorruptedData) => KvStore::format(&mut rtc, 0, 16).unwrap(),
+//!     store => store.unwrap(),
+//! };
+//! store.set(&mut rtc, BRIGHTNESS, &[80]).unwrap();
+//! assert_eq!(Some(&[80][..]), store.get(BRIGHTNESS));
+//! ```
+//!
 //! ### Enable square-wave output and select rate
 //!
 //! ```no_run
@@ -137,6 +464,153 @@
 //! let rate = SqwOutRate::Khz32_768;
 //! rtc.set_square_wave_output_rate(rate).unwrap();
 //! ```
+//!
+//! ### Configure the control register at once
+//!
+//! ```no_run
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, SqwOutRate};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//!
+//! rtc.configure()
+//!     .square_wave_output(true)
+//!     .output_rate(SqwOutRate::Hz1)
+//!     .apply()
+//!     .unwrap();
+//!
+//! let mut config = rtc.control().unwrap();
+//! config.sqwe = false;
+//! rtc.set_control(config).unwrap();
+//! ```
+//!
+//! ### Measure the MCU clock error against the RTC crystal
+//!
+//! ```no_run
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Calibration, Ds1307, SqwOutRate};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//!
+//! rtc.set_square_wave_output_rate(SqwOutRate::Khz32_768).unwrap();
+//! rtc.enable_square_wave_output().unwrap();
+//! assert_eq!(32768, rtc.square_wave_output_rate().unwrap().hz());
+//!
+//! // Timer ticks of a nominally 16 MHz MCU clock counted over 32768 periods
+//! let ticks = 16_000_800;
+//! let calibration = Calibration::new(SqwOutRate::Khz32_768, 32768, ticks).unwrap();
+//! assert_eq!(16_000_800, calibration.measured_hz());
+//! assert_eq!(Some(50), calibration.error_ppm(16_000_000));
+//! ```
+//!
+//! ### Use the SQW/OUT pin as a digital output
+//!
+//! ```no_run
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, SqwOutPin};
+//! use embedded_hal::digital::{OutputPin, StatefulOutputPin};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let rtc = Ds1307::new(dev);
+//!
+//! let mut pin = SqwOutPin::new(rtc).unwrap();
+//! pin.set_low().unwrap();
+//! pin.toggle().unwrap();
+//! let rtc = pin.destroy();
+//! ```
+//!
+//! ### Share the I²C bus with other devices
+//!
+//! The driver works with any `I2c` implementation, so the bus sharing
+//! types from [`embedded-hal-bus`] like `RefCellDevice`, `CriticalSectionDevice`
+//! or `MutexDevice` can be passed to [`Ds1307::new()`] directly.
+//!
+//! [`embedded-hal-bus`]: https://crates.io/crates/embedded-hal-bus
+//!
+//! ```no_run
+//! use core::cell::RefCell;
+//! use embedded_hal_bus::i2c::RefCellDevice;
+//! use linux_embedded_hal as hal;
+//! use ds1307::Ds1307;
+//!
+//! let bus = RefCell::new(hal::I2cdev::new("/dev/i2c-1").unwrap());
+//! let mut rtc = Ds1307::new(RefCellDevice::new(&bus));
+//! // Other devices can be created with `RefCellDevice::new(&bus)` as well.
+//! let running = rtc.running().unwrap();
+//! ```
+//!
+//! Alternatively, the bus can be borrowed for a short-lived operation only
+//! with [`Ds1307Ref`]. The bus is available again as soon as the driver
+//! instance goes out of scope.
+//!
+//! ```no_run
+//! use linux_embedded_hal as hal;
+//! use ds1307::Ds1307;
+//!
+//! let mut bus = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let running = Ds1307::new(&mut bus).running().unwrap();
+//! // `bus` can be used for other devices here.
+//! ```
+//!
+//! ### Retry failed transfers on a flaky bus
+//!
+//! ```no_run
+//! use linux_embedded_hal::{Delay, I2cdev};
+//! use ds1307::{Ds1307, RetryI2c, RetryPolicy};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let policy = RetryPolicy {
+//!     attempts: 5,
+//!     backoff_us: 500,
+//! };
+//! let i2c = RetryI2c::new(dev, Delay, policy).with_recovery(|_bus: &mut I2cdev| {
+//!     // e.g. clock SCL until the slave releases SDA
+//! });
+//! let mut rtc = Ds1307::new(i2c);
+//! rtc.set_write_verification(true);
+//! rtc.write_ram(0, &[0xAB]).unwrap();
+//! ```
+//!
+//! ### Switch outdoor lights at dusk
+//!
+//! Requires the `solar` feature.
+//!
+//! ```no_run
+//! # #[cfg(feature = "solar")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, Location, TimeDelta};
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut rtc = Ds1307::new(dev);
+//! // Madrid, RTC set to CET
+//! let location = Location::new(40.4168, -3.7038, TimeDelta::hours(1)).unwrap();
+//! let lights_on = !location.is_daylight(&mut rtc).unwrap();
+//! let today = location.sun_times_today(&mut rtc).unwrap();
+//! println!("Lights on: {lights_on}, dusk at {:?}", today.dusk);
+//! # }
+//! ```
+//!
+//! ### Serve wall-clock time in Embassy applications
+//!
+//! Requires the `embassy-time` feature. The RTC is only read on creation
+//! and afterwards every time the resync interval passes.
+//!
+//! ```no_run
+//! # #[cfg(feature = "embassy-time")]
+//! # {
+//! use linux_embedded_hal as hal;
+//! use ds1307::{Ds1307, WallClock};
+//! use embassy_time::Duration;
+//!
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let rtc = Ds1307::new(dev);
+//! let mut clock = WallClock::new(rtc, Duration::from_secs(3600)).unwrap();
+//! let now = clock.now().unwrap();
+//! # }
+//! ```
 
 #![deny(unsafe_code)]
 #![deny(missing_docs)]
@@ -151,6 +625,12 @@ pub enum Error<E> {
     I2C(E),
     /// Invalid input data.
     InvalidInputData,
+    /// Not enough free space in the user RAM region.
+    OutOfSpace,
+    /// The data stored in the user RAM is corrupted.
+    CorruptedData,
+    /// The data read back after a write did not match. See: [`Ds1307::set_write_verification()`].
+    VerifyFailed,
 }
 
 /// DS1307 driver
@@ -158,18 +638,110 @@ pub enum Error<E> {
 pub struct Ds1307<I2C> {
     /// The concrete I²C device implementation.
     i2c: I2C,
+    weekday_convention: WeekdayConvention,
+    verify_writes: bool,
+    verify_failures: VerifyFailures,
 }
 
+/// DS1307 driver borrowing the I²C bus.
+///
+/// Useful for short-lived access to a bus shared with other devices
+/// without the need for any bus sharing wrapper type.
+pub type Ds1307Ref<'a, I2C> = Ds1307<&'a mut I2C>;
+
+#[cfg(feature = "chrono")]
+mod adjust;
+#[doc(hidden)]
+pub mod bcd;
 mod datetime;
+#[cfg(feature = "chrono")]
+pub use chrono::{IsoWeek, TimeDelta, Weekday};
+#[cfg(feature = "chrono")]
 pub use rtcc::{
     DateTimeAccess, Datelike, Hours, NaiveDate, NaiveDateTime, NaiveTime, Rtcc, Timelike,
 };
+#[cfg(feature = "chrono")]
+mod backup_monitor;
+#[cfg(feature = "chrono")]
+pub use crate::backup_monitor::{BackupMonitor, BackupStatus};
+#[cfg(feature = "chrono")]
+mod boot_ledger;
+#[cfg(feature = "chrono")]
+pub use crate::boot_ledger::BootLedger;
+#[cfg(feature = "chrono")]
+mod calendar;
+#[cfg(feature = "chrono")]
+pub use crate::calendar::CalendarView;
+mod calibration;
+pub use crate::calibration::Calibration;
+#[cfg(feature = "chrono")]
+mod clock_sync;
+#[cfg(feature = "chrono")]
+pub use crate::clock_sync::{ClockSync, StartupPolicy, SyncError, SyncReport, SystemClock};
+#[cfg(feature = "chrono")]
+mod console;
+#[cfg(feature = "chrono")]
+pub use crate::console::{Console, ConsoleError};
+mod control;
+pub use crate::control::{ControlBuilder, ControlConfig};
+mod crc;
+#[cfg(feature = "chrono")]
+mod cron;
+#[cfg(feature = "chrono")]
+pub use crate::cron::{CronError, CronSchedule, DueJobs, Scheduler};
+#[cfg(feature = "chrono")]
+mod event_log;
+#[cfg(feature = "chrono")]
+pub use crate::event_log::{Event, EventIter, EventLog};
+#[cfg(feature = "fugit")]
+mod datetime_fugit;
+#[cfg(feature = "fugit")]
+pub use crate::datetime_fugit::{
+    datetime_to_instant, duration_between, instant_to_datetime, ElapsedError, EpochDuration,
+    EpochInstant, Snapshot,
+};
+#[cfg(feature = "jiff")]
+mod datetime_jiff;
+#[cfg(feature = "time")]
+mod datetime_time;
+mod kv_store;
+pub use crate::kv_store::KvStore;
+#[cfg(feature = "chrono")]
+mod monotonic_guard;
+#[cfg(feature = "chrono")]
+pub use crate::monotonic_guard::{GuardError, JumpPolicy, MonotonicGuard, TimeJump};
 mod ram;
+#[cfg(feature = "chrono")]
+mod ram_record;
+mod ram_region;
+pub use crate::ram_region::{ram_layout_is_valid, RamRegion};
+mod retry;
+pub use crate::retry::{BusRecovery, NoRecovery, RetryError, RetryI2c, RetryPolicy};
 mod run;
+#[cfg(feature = "solar")]
+mod solar;
+#[cfg(feature = "solar")]
+pub use crate::solar::{Location, SunTimes};
+mod sqw_out_pin;
+pub use crate::sqw_out_pin::SqwOutPin;
 mod square_wave;
 pub use crate::square_wave::{SqwOutLevel, SqwOutRate};
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "chrono")]
+mod text;
+#[cfg(feature = "chrono")]
+pub use crate::text::{format_datetime, TimeInput, TimeInputError};
+mod verify;
+pub use crate::verify::VerifyFailures;
+#[cfg(feature = "embassy-time")]
+mod wall_clock;
+#[cfg(feature = "embassy-time")]
+pub use crate::wall_clock::WallClock;
+mod weekday;
+pub use crate::weekday::WeekdayConvention;
 mod register_access;
-use crate::register_access::{BitFlags, Register, ADDR};
+use crate::register_access::{BitFlags, Register};
 
 impl<I2C, E> Ds1307<I2C>
 where
@@ -177,7 +749,12 @@ where
 {
     /// Create a new instance.
     pub fn new(i2c: I2C) -> Self {
-        Ds1307 { i2c }
+        Ds1307 {
+            i2c,
+            weekday_convention: WeekdayConvention::default(),
+            verify_writes: false,
+            verify_failures: VerifyFailures::default(),
+        }
     }
 
     /// Destroy driver instance, return I²C bus instance.
@@ -0,0 +1,168 @@
+use crate::ram_record::DATETIME_RECORD_SIZE;
+use crate::{DateTimeAccess, Ds1307, Error, NaiveDateTime, TimeDelta};
+use embedded_hal::i2c::I2c;
+
+/// Discontinuity detected in the RTC time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeJump {
+    /// The RTC time lies before the last returned time by the given amount.
+    Backward(TimeDelta),
+    /// The RTC time lies after the last returned time by the given amount,
+    /// which exceeds the configured maximum.
+    ForwardLarge(TimeDelta),
+}
+
+/// Handling of time jumps. See [`MonotonicGuard`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum JumpPolicy {
+    /// Return the last returned time until the RTC catches up again after a
+    /// backward jump. Large forward jumps are accepted.
+    #[default]
+    Clamp,
+    /// Return the jump as an error. The RTC time is accepted afterwards.
+    Report,
+}
+
+/// Errors reading the time through a [`MonotonicGuard`]
+#[derive(Debug)]
+pub enum GuardError<E> {
+    /// RTC error.
+    Rtc(Error<E>),
+    /// A time jump was detected (only with [`JumpPolicy::Report`]).
+    Jump(TimeJump),
+}
+
+/// Guard ensuring the date and time returned never goes backwards.
+///
+/// The guard remembers the last returned date and time and compares every new
+/// RTC reading against it, detecting backward jumps (e.g. after someone set the
+/// clock or the backup supply failed) and forward jumps larger than a
+/// configured maximum. These are handled according to the [`JumpPolicy`].
+///
+/// The last returned date and time can optionally be persisted in the user RAM,
+/// so that jumps across resets are detected as well. It then occupies
+/// [`MonotonicGuard::SIZE`] bytes of user RAM, which are written whenever it
+/// changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonotonicGuard {
+    policy: JumpPolicy,
+    max_forward: TimeDelta,
+    last: Option<NaiveDateTime>,
+    last_jump: Option<TimeJump>,
+    address_offset: Option<u8>,
+}
+
+impl MonotonicGuard {
+    /// Number of bytes of user RAM used when persisting the last returned time.
+    pub const SIZE: u8 = DATETIME_RECORD_SIZE;
+
+    /// Create a new guard without persistence.
+    ///
+    /// Forward jumps larger than `max_forward` are reported as
+    /// [`TimeJump::ForwardLarge`].
+    pub fn new(policy: JumpPolicy, max_forward: TimeDelta) -> Self {
+        MonotonicGuard {
+            policy,
+            max_forward,
+            last: None,
+            last_jump: None,
+            address_offset: None,
+        }
+    }
+
+    /// Create a new guard persisting the last returned time in the user RAM
+    /// at the given offset, loading the previously stored value.
+    ///
+    /// An invalid stored value (e.g. on first use) is ignored.
+    ///
+    /// Will return an `Error::InvalidInputData` if the stored value does not
+    /// fit in the user RAM at the given offset.
+    pub fn load<I2C, E>(
+        rtc: &mut Ds1307<I2C>,
+        address_offset: u8,
+        policy: JumpPolicy,
+        max_forward: TimeDelta,
+    ) -> Result<Self, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let mut guard = Self::new(policy, max_forward);
+        guard.last = rtc.read_datetime_record(address_offset)?;
+        guard.address_offset = Some(address_offset);
+        Ok(guard)
+    }
+
+    /// Read the date and time from the RTC, detecting time jumps.
+    pub fn now<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>) -> Result<NaiveDateTime, GuardError<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let now = rtc.datetime().map_err(GuardError::Rtc)?;
+        let jump = self.last.and_then(|last| {
+            let elapsed = now.signed_duration_since(last);
+            if elapsed < TimeDelta::zero() {
+                Some(TimeJump::Backward(-elapsed))
+            } else if elapsed > self.max_forward {
+                Some(TimeJump::ForwardLarge(elapsed))
+            } else {
+                None
+            }
+        });
+        if jump.is_some() {
+            self.last_jump = jump;
+        }
+        match (jump, self.policy) {
+            (Some(TimeJump::Backward(_)), JumpPolicy::Clamp) => Ok(self.last.unwrap_or(now)),
+            (Some(jump), JumpPolicy::Report) => {
+                self.accept(rtc, now).map_err(GuardError::Rtc)?;
+                Err(GuardError::Jump(jump))
+            }
+            _ => {
+                self.accept(rtc, now).map_err(GuardError::Rtc)?;
+                Ok(now)
+            }
+        }
+    }
+
+    /// Last returned date and time.
+    pub fn last(&self) -> Option<NaiveDateTime> {
+        self.last
+    }
+
+    /// Most recently detected time jump.
+    pub fn last_jump(&self) -> Option<TimeJump> {
+        self.last_jump
+    }
+
+    /// Forget the last returned date and time, e.g. after deliberately
+    /// setting the clock back.
+    pub fn reset<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        self.last = None;
+        self.last_jump = None;
+        self.store(rtc)
+    }
+
+    fn accept<I2C, E>(&mut self, rtc: &mut Ds1307<I2C>, now: NaiveDateTime) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        if self.last == Some(now) {
+            return Ok(());
+        }
+        self.last = Some(now);
+        self.store(rtc)
+    }
+
+    fn store<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        match self.address_offset {
+            Some(address_offset) => rtc.write_datetime_record(address_offset, self.last),
+            None => Ok(()),
+        }
+    }
+}
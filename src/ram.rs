@@ -1,7 +1,7 @@
-use crate::{Ds1307, Error, Register, ADDR};
+use crate::{Ds1307, Error, Register};
 use embedded_hal::i2c::I2c;
 
-const RAM_BYTE_COUNT: usize = (Register::RAM_END - Register::RAM_BEGIN + 1) as usize;
+pub(crate) const RAM_BYTE_COUNT: usize = (Register::RAM_END - Register::RAM_BEGIN + 1) as usize;
 
 impl<I2C, E> Ds1307<I2C>
 where
@@ -19,9 +19,7 @@ where
             return Ok(());
         }
         self.check_ram_parameters(address_offset, data)?;
-        self.i2c
-            .write_read(ADDR, &[Register::RAM_BEGIN + address_offset], &mut data[..])
-            .map_err(Error::I2C)
+        self.read_registers(Register::RAM_BEGIN + address_offset, data)
     }
 
     /// Write a data array to the user RAM starting at the given offset.
@@ -39,9 +37,7 @@ where
         let mut payload = [0; RAM_BYTE_COUNT + 1];
         payload[0] = Register::RAM_BEGIN + address_offset;
         payload[1..=data.len()].copy_from_slice(data);
-        self.i2c
-            .write(ADDR, &payload[..=data.len()])
-            .map_err(Error::I2C)
+        self.write_registers(&payload[..=data.len()])
     }
 
     fn check_ram_parameters(&self, address_offset: u8, data: &[u8]) -> Result<(), Error<E>> {
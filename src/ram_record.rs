@@ -0,0 +1,71 @@
+use crate::crc::crc8;
+use crate::datetime::{from_rtc_epoch, to_rtc_epoch};
+use crate::{Ds1307, Error, NaiveDateTime};
+use embedded_hal::i2c::I2c;
+
+/// Number of bytes of user RAM used by [`Ds1307::write_datetime_record()`].
+pub(crate) const DATETIME_RECORD_SIZE: u8 = 5;
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read `N - 1` data bytes followed by their CRC-8 from the user RAM.
+    ///
+    /// Returns `None` if the CRC does not match.
+    pub(crate) fn read_record<const N: usize>(
+        &mut self,
+        address_offset: u8,
+    ) -> Result<Option<[u8; N]>, Error<E>> {
+        let mut data = [0; N];
+        self.read_ram(address_offset, &mut data)?;
+        match data.split_last() {
+            Some((crc, payload)) if crc8(payload) == *crc => Ok(Some(data)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Write the first `N - 1` bytes of `data` followed by their CRC-8 to the user RAM.
+    pub(crate) fn write_record<const N: usize>(
+        &mut self,
+        address_offset: u8,
+        mut data: [u8; N],
+    ) -> Result<(), Error<E>> {
+        if let Some((crc, payload)) = data.split_last_mut() {
+            *crc = crc8(payload);
+        }
+        self.write_ram(address_offset, &data)
+    }
+
+    /// Read a date and time stored with [`write_datetime_record()`](Ds1307::write_datetime_record).
+    pub(crate) fn read_datetime_record(
+        &mut self,
+        address_offset: u8,
+    ) -> Result<Option<NaiveDateTime>, Error<E>> {
+        let data = self.read_record::<{ DATETIME_RECORD_SIZE as usize }>(address_offset)?;
+        Ok(data.and_then(|data| from_rtc_epoch(read_u32(&data))))
+    }
+
+    /// Store a date and time as seconds since the RTC epoch followed by a CRC-8.
+    ///
+    /// `None` or a date and time before the RTC epoch is stored as invalid record.
+    pub(crate) fn write_datetime_record(
+        &mut self,
+        address_offset: u8,
+        datetime: Option<NaiveDateTime>,
+    ) -> Result<(), Error<E>> {
+        match datetime.and_then(|datetime| to_rtc_epoch(&datetime)) {
+            Some(seconds) => {
+                let mut data = [0; DATETIME_RECORD_SIZE as usize];
+                data[..4].copy_from_slice(&seconds.to_le_bytes());
+                self.write_record(address_offset, data)
+            }
+            None => self.write_ram(address_offset, &[0; DATETIME_RECORD_SIZE as usize]),
+        }
+    }
+}
+
+/// Read a little-endian `u32` from the first 4 bytes of `data`.
+pub(crate) fn read_u32(data: &[u8]) -> u32 {
+    u32::from_le_bytes([data[0], data[1], data[2], data[3]])
+}
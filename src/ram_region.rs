@@ -0,0 +1,133 @@
+use crate::ram::RAM_BYTE_COUNT;
+use crate::{Ds1307, Error};
+use core::marker::PhantomData;
+use embedded_hal::i2c::I2c;
+
+/// Fixed region of the user RAM with `LEN` bytes starting at `OFFSET`.
+///
+/// Regions exceeding the 56 bytes of user RAM are rejected at compile time:
+///
+/// ```compile_fail
+/// use ds1307::RamRegion;
+///
+/// let region = RamRegion::<50, 8>::new();
+/// ```
+///
+/// Use [`ram_layout!`](crate::ram_layout) to declare several regions which
+/// are checked not to overlap at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RamRegion<const OFFSET: u8, const LEN: usize> {
+    _private: PhantomData<()>,
+}
+
+impl<const OFFSET: u8, const LEN: usize> RamRegion<OFFSET, LEN> {
+    /// Offset of the region in the user RAM.
+    pub const OFFSET: u8 = OFFSET;
+    /// Length of the region in bytes.
+    pub const LEN: usize = LEN;
+
+    const VALID: () = assert!(
+        LEN > 0 && OFFSET as usize + LEN <= RAM_BYTE_COUNT,
+        "RAM region exceeds the user RAM"
+    );
+
+    /// Create a new region handle.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+        RamRegion {
+            _private: PhantomData,
+        }
+    }
+
+    /// Read the contents of the region.
+    pub fn read<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<[u8; LEN], Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let mut data = [0; LEN];
+        rtc.read_ram(OFFSET, &mut data)?;
+        Ok(data)
+    }
+
+    /// Write the contents of the region.
+    pub fn write<I2C, E>(&self, rtc: &mut Ds1307<I2C>, data: &[u8; LEN]) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        rtc.write_ram(OFFSET, data)
+    }
+}
+
+impl<const OFFSET: u8, const LEN: usize> Default for RamRegion<OFFSET, LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check that all regions given as `(offset, length)` fit in the user RAM
+/// and do not overlap. Used by [`ram_layout!`](crate::ram_layout).
+#[doc(hidden)]
+pub const fn ram_layout_is_valid(regions: &[(u8, usize)]) -> bool {
+    let mut i = 0;
+    while i < regions.len() {
+        let (offset, length) = regions[i];
+        if length == 0 || offset as usize + length > RAM_BYTE_COUNT {
+            return false;
+        }
+        let mut j = i + 1;
+        while j < regions.len() {
+            let (other_offset, other_length) = regions[j];
+            if (offset as usize) < other_offset as usize + other_length
+                && (other_offset as usize) < offset as usize + length
+            {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Declare a layout of user RAM regions as [`RamRegion`] types.
+///
+/// Each entry declares a type alias with the given offset and length.
+/// Regions exceeding the user RAM or overlapping each other are rejected
+/// at compile time.
+///
+/// ```
+/// use ds1307::ram_layout;
+///
+/// ram_layout! {
+///     /// Device settings
+///     pub Settings: 0, 4;
+///     /// Boot counter
+///     pub Counter: 4, 2;
+/// }
+///
+/// let settings = Settings::new();
+/// assert_eq!(4, Counter::OFFSET);
+/// ```
+///
+/// ```compile_fail
+/// use ds1307::ram_layout;
+///
+/// ram_layout! {
+///     pub Settings: 0, 4;
+///     pub Counter: 3, 2;
+/// }
+/// ```
+#[macro_export]
+macro_rules! ram_layout {
+    ($( $(#[$attr:meta])* $vis:vis $name:ident : $offset:expr, $len:expr; )+) => {
+        $(
+            $(#[$attr])*
+            $vis type $name = $crate::RamRegion<$offset, $len>;
+        )+
+        const _: () = assert!(
+            $crate::ram_layout_is_valid(&[$(($offset, $len)),+]),
+            "RAM regions exceed the user RAM or overlap"
+        );
+    };
+}
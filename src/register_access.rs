@@ -68,15 +68,26 @@ where
     }
 
     pub(crate) fn write_register(&mut self, register: u8, data: u8) -> Result<(), Error<E>> {
-        let payload: [u8; 2] = [register, data];
-        self.i2c.write(ADDR, &payload).map_err(Error::I2C)
+        self.write_registers(&[register, data])
     }
 
-    pub(crate) fn read_register(&mut self, register: u8) -> Result<u8, Error<E>> {
-        let mut data = [0];
+    /// Write consecutive registers. The first payload byte is the start register.
+    pub(crate) fn write_registers(&mut self, payload: &[u8]) -> Result<(), Error<E>> {
+        if self.verify_writes {
+            return self.write_verified(payload);
+        }
+        self.i2c.write(ADDR, payload).map_err(Error::I2C)
+    }
+
+    pub(crate) fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Error<E>> {
         self.i2c
-            .write_read(ADDR, &[register], &mut data)
+            .write_read(ADDR, &[register], data)
             .map_err(Error::I2C)
-            .and(Ok(data[0]))
+    }
+
+    pub(crate) fn read_register(&mut self, register: u8) -> Result<u8, Error<E>> {
+        let mut data = [0];
+        self.read_registers(register, &mut data)?;
+        Ok(data[0])
     }
 }
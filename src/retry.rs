@@ -0,0 +1,192 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{self, ErrorKind, ErrorType, I2c, Operation, SevenBitAddress};
+
+/// Retry policy for I²C transfers. See [`RetryI2c`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Number of attempts for each transfer, including the first one.
+    /// There is always at least one attempt.
+    pub attempts: u8,
+    /// Delay before the first retry in microseconds.
+    /// The delay is doubled for every further retry.
+    pub backoff_us: u32,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 1 ms initial backoff.
+    fn default() -> Self {
+        RetryPolicy {
+            attempts: 3,
+            backoff_us: 1_000,
+        }
+    }
+}
+
+/// Bus recovery action performed after a failed transfer.
+///
+/// This can be used to unstick a slave holding SDA low by clocking SCL,
+/// or to reinitialize the I²C peripheral. Implemented for closures taking
+/// the I²C bus.
+pub trait BusRecovery<I2C> {
+    /// Try to bring the bus back into a usable state.
+    fn recover(&mut self, i2c: &mut I2C);
+}
+
+impl<I2C, F> BusRecovery<I2C> for F
+where
+    F: FnMut(&mut I2C),
+{
+    fn recover(&mut self, i2c: &mut I2C) {
+        self(i2c)
+    }
+}
+
+/// No bus recovery action.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct NoRecovery;
+
+impl<I2C> BusRecovery<I2C> for NoRecovery {
+    fn recover(&mut self, _i2c: &mut I2C) {}
+}
+
+/// Error of an I²C transfer through [`RetryI2c`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RetryError<E> {
+    /// I²C bus error of the last attempt
+    I2C(E),
+}
+
+impl<E> i2c::Error for RetryError<E>
+where
+    E: i2c::Error,
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            RetryError::I2C(e) => e.kind(),
+        }
+    }
+}
+
+/// I²C bus wrapper retrying failed transfers.
+///
+/// Every register access of the driver goes through the I²C bus, so passing
+/// this wrapper to [`Ds1307::new()`](crate::Ds1307::new) applies the retry
+/// policy to all of them. After each failed attempt the bus recovery action
+/// is run and the backoff delay is waited.
+///
+/// The wrapper does not check the written data. Use
+/// [`set_write_verification()`](crate::Ds1307::set_write_verification) for that.
+#[derive(Debug)]
+pub struct RetryI2c<I2C, D, R = NoRecovery> {
+    i2c: I2C,
+    delay: D,
+    policy: RetryPolicy,
+    recovery: R,
+    retries: u32,
+}
+
+impl<I2C, D> RetryI2c<I2C, D> {
+    /// Create a new instance without bus recovery action.
+    pub fn new(i2c: I2C, delay: D, policy: RetryPolicy) -> Self {
+        RetryI2c {
+            i2c,
+            delay,
+            policy,
+            recovery: NoRecovery,
+            retries: 0,
+        }
+    }
+}
+
+impl<I2C, D, R> RetryI2c<I2C, D, R> {
+    /// Set the bus recovery action run after each failed attempt.
+    pub fn with_recovery<R2>(self, recovery: R2) -> RetryI2c<I2C, D, R2>
+    where
+        R2: BusRecovery<I2C>,
+    {
+        RetryI2c {
+            i2c: self.i2c,
+            delay: self.delay,
+            policy: self.policy,
+            recovery,
+            retries: self.retries,
+        }
+    }
+
+    /// Total number of retries performed so far.
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Destroy the wrapper, return the I²C bus and delay instances.
+    pub fn destroy(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+}
+
+impl<I2C, D, R> RetryI2c<I2C, D, R>
+where
+    I2C: I2c,
+    D: DelayNs,
+    R: BusRecovery<I2C>,
+{
+    fn retry<F>(&mut self, mut transfer: F) -> Result<(), RetryError<I2C::Error>>
+    where
+        F: FnMut(&mut I2C) -> Result<(), I2C::Error>,
+    {
+        let mut backoff_us = self.policy.backoff_us;
+        let mut attempt = 1;
+        loop {
+            match transfer(&mut self.i2c) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= self.policy.attempts => return Err(RetryError::I2C(e)),
+                Err(_) => {
+                    self.recovery.recover(&mut self.i2c);
+                    self.delay.delay_us(backoff_us);
+                    backoff_us = backoff_us.saturating_mul(2);
+                    attempt += 1;
+                    self.retries = self.retries.wrapping_add(1);
+                }
+            }
+        }
+    }
+}
+
+impl<I2C, D, R> ErrorType for RetryI2c<I2C, D, R>
+where
+    I2C: I2c,
+{
+    type Error = RetryError<I2C::Error>;
+}
+
+impl<I2C, D, R> I2c for RetryI2c<I2C, D, R>
+where
+    I2C: I2c,
+    D: DelayNs,
+    R: BusRecovery<I2C>,
+{
+    fn read(&mut self, address: SevenBitAddress, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.retry(|i2c| i2c.read(address, read))
+    }
+
+    fn write(&mut self, address: SevenBitAddress, write: &[u8]) -> Result<(), Self::Error> {
+        self.retry(|i2c| i2c.write(address, write))
+    }
+
+    fn write_read(
+        &mut self,
+        address: SevenBitAddress,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.retry(|i2c| i2c.write_read(address, write, read))
+    }
+
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.retry(|i2c| i2c.transaction(address, operations))
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{DateTimeAccess, Ds1307, Error};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use core::f64::consts::PI;
+use embedded_hal::i2c::I2c;
+use libm::{acos, cos, sin, tan};
+
+/// Zenith angle of the sun center at sunrise and sunset in degrees,
+/// accounting for atmospheric refraction and the solar disc radius.
+const SUNRISE_ZENITH: f64 = 90.833;
+/// Zenith angle of the sun center at civil dawn and dusk in degrees.
+const CIVIL_ZENITH: f64 = 96.0;
+/// Largest supported offset of the local time to UTC in hours.
+const MAX_UTC_OFFSET_HOURS: i64 = 18;
+
+/// Times of the solar events of a day in local time
+///
+/// An event is `None` if the sun does not cross the corresponding altitude
+/// on that day, e.g. during polar day or night.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunTimes {
+    /// Begin of civil twilight (sun 6° below the horizon).
+    pub dawn: Option<NaiveDateTime>,
+    /// Sunrise.
+    pub sunrise: Option<NaiveDateTime>,
+    /// Sunset.
+    pub sunset: Option<NaiveDateTime>,
+    /// End of civil twilight (sun 6° below the horizon).
+    pub dusk: Option<NaiveDateTime>,
+}
+
+/// Geographic location for solar calculations. Requires the `solar` feature.
+///
+/// The RTC is assumed to hold the local time at the given offset from UTC.
+/// The calculations follow the NOAA approximations and are accurate to about
+/// one or two minutes outside of the polar regions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    latitude: f64,
+    longitude: f64,
+    utc_offset: TimeDelta,
+}
+
+impl Location {
+    /// Create a new location.
+    ///
+    /// The latitude is given in degrees north [-90, 90], the longitude in
+    /// degrees east [-180, 180] and the offset of the local time to UTC in
+    /// [-18 h, 18 h]. Returns `None` if any of them is out of range.
+    pub fn new(latitude: f64, longitude: f64, utc_offset: TimeDelta) -> Option<Self> {
+        if !(-90.0..=90.0).contains(&latitude)
+            || !(-180.0..=180.0).contains(&longitude)
+            || utc_offset.abs() > TimeDelta::hours(MAX_UTC_OFFSET_HOURS)
+        {
+            return None;
+        }
+        Some(Location {
+            latitude,
+            longitude,
+            utc_offset,
+        })
+    }
+
+    /// Latitude in degrees north.
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// Longitude in degrees east.
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Offset of the local time from UTC.
+    pub fn utc_offset(&self) -> TimeDelta {
+        self.utc_offset
+    }
+
+    /// Calculate the solar events of the given local date.
+    pub fn sun_times(&self, date: NaiveDate) -> SunTimes {
+        let position = SolarPosition::new(date.ordinal0(), 12.0);
+        let event = |zenith: f64, sign: f64| {
+            let hour_angle = self.hour_angle(&position, zenith)?;
+            let utc_minutes =
+                720.0 - 4.0 * (self.longitude + sign * hour_angle) - position.equation_of_time;
+            let seconds = libm::round(utc_minutes * 60.0) as i64;
+            date.and_time(NaiveTime::MIN)
+                .checked_add_signed(TimeDelta::seconds(seconds) + self.utc_offset)
+        };
+        SunTimes {
+            dawn: event(CIVIL_ZENITH, 1.0),
+            sunrise: event(SUNRISE_ZENITH, 1.0),
+            sunset: event(SUNRISE_ZENITH, -1.0),
+            dusk: event(CIVIL_ZENITH, -1.0),
+        }
+    }
+
+    /// Calculate the elevation of the sun above the horizon in degrees at the
+    /// given local date and time, without atmospheric refraction.
+    pub fn sun_elevation(&self, datetime: &NaiveDateTime) -> f64 {
+        let utc_seconds =
+            i64::from(datetime.num_seconds_from_midnight()) - self.utc_offset.num_seconds();
+        let utc_day = (i64::from(datetime.ordinal0()) + utc_seconds.div_euclid(86_400))
+            .rem_euclid(366) as u32;
+        let utc_minutes = utc_seconds.rem_euclid(86_400) as f64 / 60.0;
+        let position = SolarPosition::new(utc_day, utc_minutes / 60.0);
+        let true_solar_minutes = utc_minutes + position.equation_of_time + 4.0 * self.longitude;
+        let hour_angle = (true_solar_minutes / 4.0 - 180.0).to_radians();
+        let latitude = self.latitude.to_radians();
+        let cos_zenith = sin(latitude) * sin(position.declination)
+            + cos(latitude) * cos(position.declination) * cos(hour_angle);
+        90.0 - acos(cos_zenith.clamp(-1.0, 1.0)).to_degrees()
+    }
+
+    /// Whether the sun is above the horizon at the given local date and time.
+    pub fn is_daylight_at(&self, datetime: &NaiveDateTime) -> bool {
+        self.sun_elevation(datetime) > 90.0 - SUNRISE_ZENITH
+    }
+
+    /// Whether it is brighter than civil twilight at the given local date and time.
+    pub fn is_civil_daylight_at(&self, datetime: &NaiveDateTime) -> bool {
+        self.sun_elevation(datetime) > 90.0 - CIVIL_ZENITH
+    }
+
+    /// Read the date from the RTC and calculate its solar events.
+    ///
+    /// Will return an `Error::InvalidInputData` if the device contains an invalid date.
+    pub fn sun_times_today<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<SunTimes, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        Ok(self.sun_times(rtc.datetime()?.date()))
+    }
+
+    /// Read the date and time from the RTC and return whether the sun is above
+    /// the horizon.
+    ///
+    /// Will return an `Error::InvalidInputData` if the device contains an invalid date.
+    pub fn is_daylight<I2C, E>(&self, rtc: &mut Ds1307<I2C>) -> Result<bool, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        Ok(self.is_daylight_at(&rtc.datetime()?))
+    }
+
+    /// Hour angle of the sun at the given zenith angle in degrees.
+    fn hour_angle(&self, position: &SolarPosition, zenith: f64) -> Option<f64> {
+        let latitude = self.latitude.to_radians();
+        let cos_hour_angle = cos(zenith.to_radians()) / (cos(latitude) * cos(position.declination))
+            - tan(latitude) * tan(position.declination);
+        if (-1.0..=1.0).contains(&cos_hour_angle) {
+            Some(acos(cos_hour_angle).to_degrees())
+        } else {
+            None
+        }
+    }
+}
+
+/// Solar declination and equation of time
+struct SolarPosition {
+    /// Declination in radians.
+    declination: f64,
+    /// Equation of time in minutes.
+    equation_of_time: f64,
+}
+
+impl SolarPosition {
+    fn new(day_of_year0: u32, hour: f64) -> Self {
+        let gamma = 2.0 * PI / 365.0 * (f64::from(day_of_year0) + (hour - 12.0) / 24.0);
+        let equation_of_time = 229.18
+            * (0.000075 + 0.001868 * cos(gamma)
+                - 0.032077 * sin(gamma)
+                - 0.014615 * cos(2.0 * gamma)
+                - 0.040849 * sin(2.0 * gamma));
+        let declination = 0.006918 - 0.399912 * cos(gamma) + 0.070257 * sin(gamma)
+            - 0.006758 * cos(2.0 * gamma)
+            + 0.000907 * sin(2.0 * gamma)
+            - 0.002697 * cos(3.0 * gamma)
+            + 0.00148 * sin(3.0 * gamma);
+        SolarPosition {
+            declination,
+            equation_of_time,
+        }
+    }
+}
@@ -25,6 +25,75 @@ pub enum SqwOutLevel {
     High,
 }
 
+impl SqwOutRate {
+    /// Output frequency in Hz.
+    pub const fn hz(self) -> u32 {
+        match self {
+            SqwOutRate::Hz1 => 1,
+            SqwOutRate::Khz4_096 => 4096,
+            SqwOutRate::Khz8_192 => 8192,
+            SqwOutRate::Khz32_768 => 32768,
+        }
+    }
+
+    /// Rate corresponding to an output frequency in Hz, if supported.
+    pub const fn from_hz(hz: u32) -> Option<Self> {
+        match hz {
+            1 => Some(SqwOutRate::Hz1),
+            4096 => Some(SqwOutRate::Khz4_096),
+            8192 => Some(SqwOutRate::Khz8_192),
+            32768 => Some(SqwOutRate::Khz32_768),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_register(data: u8) -> Self {
+        let rs1 = (data & BitFlags::OUTRATERS1) != 0;
+        let rs0 = (data & BitFlags::OUTRATERS0) != 0;
+        match (rs1, rs0) {
+            (false, false) => SqwOutRate::Hz1,
+            (false, true) => SqwOutRate::Khz4_096,
+            (true, false) => SqwOutRate::Khz8_192,
+            (true, true) => SqwOutRate::Khz32_768,
+        }
+    }
+
+    pub(crate) fn register_bits(self) -> u8 {
+        match self {
+            SqwOutRate::Hz1 => 0,
+            SqwOutRate::Khz4_096 => BitFlags::OUTRATERS0,
+            SqwOutRate::Khz8_192 => BitFlags::OUTRATERS1,
+            SqwOutRate::Khz32_768 => BitFlags::OUTRATERS1 | BitFlags::OUTRATERS0,
+        }
+    }
+}
+
+impl From<SqwOutRate> for u32 {
+    fn from(rate: SqwOutRate) -> Self {
+        rate.hz()
+    }
+}
+
+#[cfg(feature = "fugit")]
+impl SqwOutRate {
+    /// Output frequency as a `fugit` rate.
+    pub const fn rate(self) -> fugit::HertzU32 {
+        fugit::HertzU32::from_raw(self.hz())
+    }
+
+    /// Rate corresponding to a `fugit` output frequency, if supported.
+    pub const fn from_rate(rate: fugit::HertzU32) -> Option<Self> {
+        Self::from_hz(rate.raw())
+    }
+}
+
+#[cfg(feature = "fugit")]
+impl From<SqwOutRate> for fugit::HertzU32 {
+    fn from(rate: SqwOutRate) -> Self {
+        rate.rate()
+    }
+}
+
 impl<I2C, E> Ds1307<I2C>
 where
     I2C: I2c<Error = E>,
@@ -67,26 +136,13 @@ where
     /// Read square-wave output rate control bits.
     pub fn square_wave_output_rate(&mut self) -> Result<SqwOutRate, Error<E>> {
         let data = self.read_register(Register::SQWOUT)?;
-        let rs1 = (data & BitFlags::OUTRATERS1) != 0;
-        let rs0 = (data & BitFlags::OUTRATERS0) != 0;
-        match (rs1, rs0) {
-            (false, false) => Ok(SqwOutRate::Hz1),
-            (false, true) => Ok(SqwOutRate::Khz4_096),
-            (true, false) => Ok(SqwOutRate::Khz8_192),
-            (true, true) => Ok(SqwOutRate::Khz32_768),
-        }
+        Ok(SqwOutRate::from_register(data))
     }
 
     /// Set square-wave output rate.
     pub fn set_square_wave_output_rate(&mut self, rate: SqwOutRate) -> Result<(), Error<E>> {
         let data = self.read_register(Register::SQWOUT)?;
-        let data = data & !BitFlags::OUTRATERS1 & !BitFlags::OUTRATERS0;
-        let sqwout = match rate {
-            SqwOutRate::Hz1 => data,
-            SqwOutRate::Khz4_096 => data | BitFlags::OUTRATERS0,
-            SqwOutRate::Khz8_192 => data | BitFlags::OUTRATERS1,
-            SqwOutRate::Khz32_768 => data | BitFlags::OUTRATERS1 | BitFlags::OUTRATERS0,
-        };
-        self.write_register(Register::SQWOUT, sqwout)
+        let data = data & !(BitFlags::OUTRATERS1 | BitFlags::OUTRATERS0);
+        self.write_register(Register::SQWOUT, data | rate.register_bits())
     }
 }
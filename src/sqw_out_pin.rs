@@ -0,0 +1,76 @@
+use crate::{Ds1307, Error, SqwOutLevel};
+use embedded_hal::digital::{self, ErrorKind, ErrorType, OutputPin, StatefulOutputPin};
+use embedded_hal::i2c::I2c;
+
+/// SQW/OUT pin used as a static output.
+///
+/// With the square-wave output disabled, the SQW/OUT pin is an open-drain
+/// output driven by the output level control bit. This wrapper implements the
+/// `embedded-hal` digital output traits on top of it, so that it can be
+/// passed to any driver expecting a GPIO.
+///
+/// Note that the pin is open-drain, so setting it high requires a pull-up resistor.
+#[derive(Debug)]
+pub struct SqwOutPin<I2C> {
+    rtc: Ds1307<I2C>,
+}
+
+impl<I2C, E> SqwOutPin<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new instance.
+    ///
+    /// Disables the square-wave output. The output level is kept.
+    pub fn new(mut rtc: Ds1307<I2C>) -> Result<Self, Error<E>> {
+        rtc.disable_square_wave_output()?;
+        Ok(SqwOutPin { rtc })
+    }
+
+    /// Destroy the pin instance, return the DS1307 driver instance.
+    pub fn destroy(self) -> Ds1307<I2C> {
+        self.rtc
+    }
+}
+
+impl<E> digital::Error for Error<E>
+where
+    E: core::fmt::Debug,
+{
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<I2C> ErrorType for SqwOutPin<I2C>
+where
+    I2C: I2c,
+{
+    type Error = Error<I2C::Error>;
+}
+
+impl<I2C> OutputPin for SqwOutPin<I2C>
+where
+    I2C: I2c,
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.rtc.set_square_wave_output_level(SqwOutLevel::Low)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.rtc.set_square_wave_output_level(SqwOutLevel::High)
+    }
+}
+
+impl<I2C> StatefulOutputPin for SqwOutPin<I2C>
+where
+    I2C: I2c,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.rtc.square_wave_output_level()? == SqwOutLevel::High)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.rtc.square_wave_output_level()? == SqwOutLevel::Low)
+    }
+}
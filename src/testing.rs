@@ -0,0 +1,201 @@
+//! Expectation builders for testing application code using this driver.
+//!
+//! Each function returns the I²C transactions the driver performs for the
+//! corresponding operation, to be used with the `embedded-hal-mock` I²C mock.
+//! Several expectations can be combined with `concat()`.
+//!
+//! The day of the week is encoded with the default `WeekdayConvention`.
+//!
+//! ```
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use ds1307::testing::{expect_datetime_read, expect_ram_write};
+//! use ds1307::{DateTimeAccess, Ds1307, NaiveDate};
+//! use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+//!
+//! let datetime = NaiveDate::from_ymd_opt(2024, 2, 29)
+//!     .unwrap()
+//!     .and_hms_opt(13, 45, 0)
+//!     .unwrap();
+//! let expectations = [
+//!     expect_datetime_read(datetime),
+//!     expect_ram_write(0, &[0xAB, 0xCD]),
+//! ]
+//! .concat();
+//! let mut rtc = Ds1307::new(I2cMock::new(&expectations));
+//! assert_eq!(datetime, rtc.datetime().unwrap());
+//! rtc.write_ram(0, &[0xAB, 0xCD]).unwrap();
+//! rtc.destroy().done();
+//! # }
+//! ```
+
+extern crate alloc;
+
+#[cfg(feature = "chrono")]
+use crate::bcd::decimal_to_packed_bcd;
+use crate::{BitFlags, Register};
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+pub use embedded_hal_mock::eh1::i2c::Transaction;
+
+/// Device I²C address.
+pub const ADDRESS: u8 = crate::register_access::ADDR;
+
+fn ch_flag(halted: bool) -> u8 {
+    if halted {
+        BitFlags::CH
+    } else {
+        0
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn time_registers(time: &NaiveTime, halted: bool) -> [u8; 3] {
+    [
+        decimal_to_packed_bcd(time.second() as u8) | ch_flag(halted),
+        decimal_to_packed_bcd(time.minute() as u8),
+        decimal_to_packed_bcd(time.hour() as u8),
+    ]
+}
+
+#[cfg(feature = "chrono")]
+fn date_registers(date: &NaiveDate) -> [u8; 4] {
+    [
+        date.weekday().number_from_sunday() as u8,
+        decimal_to_packed_bcd(date.day() as u8),
+        decimal_to_packed_bcd(date.month() as u8),
+        decimal_to_packed_bcd((date.year() - 2000) as u8),
+    ]
+}
+
+/// Expect reading the date and time with `datetime()` while the clock is running.
+///
+/// The date must be in the range 2000-2099.
+#[cfg(feature = "chrono")]
+pub fn expect_datetime_read(datetime: NaiveDateTime) -> Vec<Transaction> {
+    let mut data = time_registers(&datetime.time(), false).to_vec();
+    data.extend_from_slice(&date_registers(&datetime.date()));
+    vec![Transaction::write_read(
+        ADDRESS,
+        vec![Register::SECONDS],
+        data,
+    )]
+}
+
+/// Expect setting the date and time with `set_datetime()`.
+///
+/// `halted` is the clock halt status of the device, which is kept.
+/// The date must be in the range 2000-2099.
+#[cfg(feature = "chrono")]
+pub fn expect_set_datetime(datetime: NaiveDateTime, halted: bool) -> Vec<Transaction> {
+    let mut payload = vec![Register::SECONDS];
+    payload.extend_from_slice(&time_registers(&datetime.time(), halted));
+    payload.extend_from_slice(&date_registers(&datetime.date()));
+    vec![
+        Transaction::write_read(ADDRESS, vec![Register::SECONDS], vec![ch_flag(halted)]),
+        Transaction::write(ADDRESS, payload),
+    ]
+}
+
+/// Expect reading the date with `date()`.
+///
+/// The date must be in the range 2000-2099.
+#[cfg(feature = "chrono")]
+pub fn expect_date_read(date: NaiveDate) -> Vec<Transaction> {
+    vec![Transaction::write_read(
+        ADDRESS,
+        vec![Register::DOM],
+        date_registers(&date)[1..].to_vec(),
+    )]
+}
+
+/// Expect setting the date with `set_date()`.
+///
+/// The date must be in the range 2000-2099.
+#[cfg(feature = "chrono")]
+pub fn expect_set_date(date: NaiveDate) -> Vec<Transaction> {
+    let mut payload = vec![Register::DOW];
+    payload.extend_from_slice(&date_registers(&date));
+    vec![Transaction::write(ADDRESS, payload)]
+}
+
+/// Expect reading the time with `time()` while the clock is running.
+#[cfg(feature = "chrono")]
+pub fn expect_time_read(time: NaiveTime) -> Vec<Transaction> {
+    vec![Transaction::write_read(
+        ADDRESS,
+        vec![Register::SECONDS],
+        time_registers(&time, false).to_vec(),
+    )]
+}
+
+/// Expect setting the time with `set_time()`.
+///
+/// `halted` is the clock halt status of the device, which is kept.
+#[cfg(feature = "chrono")]
+pub fn expect_set_time(time: NaiveTime, halted: bool) -> Vec<Transaction> {
+    let mut payload = vec![Register::SECONDS];
+    payload.extend_from_slice(&time_registers(&time, halted));
+    vec![
+        Transaction::write_read(ADDRESS, vec![Register::SECONDS], vec![ch_flag(halted)]),
+        Transaction::write(ADDRESS, payload),
+    ]
+}
+
+/// Expect reading whether the clock is running with `running()`.
+pub fn expect_running_read(running: bool) -> Vec<Transaction> {
+    vec![Transaction::write_read(
+        ADDRESS,
+        vec![Register::SECONDS],
+        vec![ch_flag(!running)],
+    )]
+}
+
+/// Expect halting the clock with `halt()`.
+///
+/// `running` is the status of the clock before the call.
+pub fn expect_halt(running: bool) -> Vec<Transaction> {
+    let mut transactions = expect_running_read(running);
+    if running {
+        transactions.push(Transaction::write(
+            ADDRESS,
+            vec![Register::SECONDS, BitFlags::CH],
+        ));
+    }
+    transactions
+}
+
+/// Expect starting the clock with `set_running()`.
+///
+/// `running` is the status of the clock before the call.
+pub fn expect_set_running(running: bool) -> Vec<Transaction> {
+    let mut transactions = expect_running_read(running);
+    if !running {
+        transactions.push(Transaction::write(ADDRESS, vec![Register::SECONDS, 0]));
+    }
+    transactions
+}
+
+/// Expect reading `data` from the user RAM with `read_ram()`.
+pub fn expect_ram_read(address_offset: u8, data: &[u8]) -> Vec<Transaction> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+    vec![Transaction::write_read(
+        ADDRESS,
+        vec![Register::RAM_BEGIN + address_offset],
+        data.to_vec(),
+    )]
+}
+
+/// Expect writing `data` to the user RAM with `write_ram()`.
+pub fn expect_ram_write(address_offset: u8, data: &[u8]) -> Vec<Transaction> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+    let mut payload = vec![Register::RAM_BEGIN + address_offset];
+    payload.extend_from_slice(data);
+    vec![Transaction::write(ADDRESS, payload)]
+}
@@ -0,0 +1,317 @@
+use crate::bcd::decimal_to_packed_bcd;
+use crate::{BitFlags, DateTimeAccess, Ds1307, Error, Register};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use core::fmt::{self, Write};
+use core::str::FromStr;
+use embedded_hal::i2c::I2c;
+use rtcc::Hours;
+
+/// Errors parsing a [`TimeInput`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeInputError {
+    /// The text does not match any of the accepted formats.
+    Syntax,
+    /// A value is out of range, e.g. `25:00` or `2024-02-30`.
+    OutOfRange,
+}
+
+/// Date and time input parsed from text, e.g. on a serial console
+///
+/// Accepted formats:
+/// - Date and time: `2024-02-01 13:45:00` or `2024-02-01T13:45`.
+/// - Time of day in 24-hour format: `13:45` or `13:45:30`.
+/// - Time of day in 12-hour format: `1:45pm` or `1:45:30 AM`.
+/// - Relative adjustment: `+90s`, `-2m`, `+1h30m` or `+1d`. A number without
+///   unit is taken as seconds.
+///
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use ds1307::{Hours, TimeInput};
+///
+/// assert_eq!(Ok(TimeInput::Adjust(90)), "+90s".parse());
+/// assert_eq!(
+///     Ok(TimeInput::Time { hours: Hours::PM(1), minutes: 45, seconds: 0 }),
+///     "1:45pm".parse()
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeInput {
+    /// Absolute date and time.
+    DateTime(NaiveDateTime),
+    /// Time of day. The hours keep the 12-hour or 24-hour format of the input.
+    Time {
+        /// Hours.
+        hours: Hours,
+        /// Minutes [0-59].
+        minutes: u8,
+        /// Seconds [0-59].
+        seconds: u8,
+    },
+    /// Relative adjustment in seconds.
+    Adjust(i32),
+}
+
+impl TimeInput {
+    /// Parse a date and time input.
+    pub fn parse(text: &str) -> Result<Self, TimeInputError> {
+        let mut cursor = Cursor::new(text.trim());
+        let input = match cursor.peek() {
+            Some(b'+') | Some(b'-') => cursor.adjustment()?,
+            _ if text.contains('-') => TimeInput::DateTime(cursor.datetime()?),
+            _ => cursor.time_of_day()?,
+        };
+        if cursor.is_done() {
+            Ok(input)
+        } else {
+            Err(TimeInputError::Syntax)
+        }
+    }
+}
+
+impl FromStr for TimeInput {
+    type Err = TimeInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TimeInput::parse(s)
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Cursor {
+            bytes: text.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn is_done(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let matches = self.peek().map_or(false, |b| b.eq_ignore_ascii_case(&byte));
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), TimeInputError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(TimeInputError::Syntax)
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.eat(b' ') {}
+    }
+
+    /// Parse a number of up to `max_digits` digits.
+    fn number(&mut self, max_digits: usize) -> Result<u32, TimeInputError> {
+        let start = self.position;
+        let mut value: u32 = 0;
+        while let Some(digit) = self.peek().filter(u8::is_ascii_digit) {
+            if self.position - start == max_digits {
+                return Err(TimeInputError::OutOfRange);
+            }
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u32::from(digit - b'0')))
+                .ok_or(TimeInputError::OutOfRange)?;
+            self.position += 1;
+        }
+        if self.position == start {
+            Err(TimeInputError::Syntax)
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn datetime(&mut self) -> Result<NaiveDateTime, TimeInputError> {
+        let year = self.number(4)?;
+        self.expect(b'-')?;
+        let month = self.number(2)?;
+        self.expect(b'-')?;
+        let day = self.number(2)?;
+        if !self.eat(b'T') {
+            self.expect(b' ')?;
+            self.skip_spaces();
+        }
+        let (hour, minute, second) = self.hms()?;
+        NaiveDate::from_ymd_opt(year as i32, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .ok_or(TimeInputError::OutOfRange)
+    }
+
+    fn time_of_day(&mut self) -> Result<TimeInput, TimeInputError> {
+        let (hour, minutes, seconds) = self.hms()?;
+        self.skip_spaces();
+        let hours = if self.eat(b'a') {
+            self.expect(b'm')?;
+            Hours::AM(hour as u8)
+        } else if self.eat(b'p') {
+            self.expect(b'm')?;
+            Hours::PM(hour as u8)
+        } else {
+            Hours::H24(hour as u8)
+        };
+        let valid = match hours {
+            Hours::H24(h) => h < 24,
+            Hours::AM(h) | Hours::PM(h) => (1..=12).contains(&h),
+        };
+        if !valid || minutes > 59 || seconds > 59 {
+            return Err(TimeInputError::OutOfRange);
+        }
+        Ok(TimeInput::Time {
+            hours,
+            minutes: minutes as u8,
+            seconds: seconds as u8,
+        })
+    }
+
+    fn hms(&mut self) -> Result<(u32, u32, u32), TimeInputError> {
+        let hour = self.number(2)?;
+        self.expect(b':')?;
+        let minute = self.number(2)?;
+        let second = if self.eat(b':') { self.number(2)? } else { 0 };
+        NaiveTime::from_hms_opt(hour, minute, second).ok_or(TimeInputError::OutOfRange)?;
+        Ok((hour, minute, second))
+    }
+
+    fn adjustment(&mut self) -> Result<TimeInput, TimeInputError> {
+        let negative = self.eat(b'-');
+        if !negative {
+            self.expect(b'+')?;
+        }
+        let mut total: i64 = 0;
+        loop {
+            let value = i64::from(self.number(10)?);
+            let unit = match self.peek().map(|b| b.to_ascii_lowercase()) {
+                Some(b's') => 1,
+                Some(b'm') => 60,
+                Some(b'h') => 3600,
+                Some(b'd') => 86400,
+                _ => {
+                    total += value;
+                    break;
+                }
+            };
+            self.position += 1;
+            total += value * unit;
+            // Bounded by the magnitude of `i32::MIN`, checked after applying the sign.
+            if total > -i64::from(i32::MIN) {
+                return Err(TimeInputError::OutOfRange);
+            }
+            if self.is_done() {
+                break;
+            }
+        }
+        let total = if negative { -total } else { total };
+        i32::try_from(total)
+            .map(TimeInput::Adjust)
+            .map_err(|_| TimeInputError::OutOfRange)
+    }
+}
+
+/// Write the date and time to `out` according to `pattern`.
+///
+/// The pattern may contain the following specifiers:
+/// `%Y` (year), `%y` (year without century), `%m` (month), `%d` (day),
+/// `%H` (hour, 24-hour format), `%I` (hour, 12-hour format), `%p` (AM/PM),
+/// `%M` (minute), `%S` (second), `%j` (day of the year), `%a` (abbreviated
+/// weekday name), `%b` (abbreviated month name) and `%%`.
+/// Returns an error on unknown specifiers.
+///
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use ds1307::{format_datetime, NaiveDate};
+///
+/// let datetime = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap().and_hms_opt(13, 45, 0).unwrap();
+/// let mut out = String::new();
+/// format_datetime(&mut out, &datetime, "%a %d %b %Y %I:%M %p").unwrap();
+/// assert_eq!("Thu 01 Feb 2024 01:45 PM", out);
+/// # }
+/// ```
+pub fn format_datetime<W: Write>(
+    out: &mut W,
+    datetime: &NaiveDateTime,
+    pattern: &str,
+) -> fmt::Result {
+    const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.write_char(c)?;
+            continue;
+        }
+        match chars.next().ok_or(fmt::Error)? {
+            'Y' => write!(out, "{:04}", datetime.year())?,
+            'y' => write!(out, "{:02}", datetime.year() % 100)?,
+            'm' => write!(out, "{:02}", datetime.month())?,
+            'd' => write!(out, "{:02}", datetime.day())?,
+            'H' => write!(out, "{:02}", datetime.hour())?,
+            'I' => write!(out, "{:02}", datetime.hour12().1)?,
+            'p' => out.write_str(if datetime.hour12().0 { "PM" } else { "AM" })?,
+            'M' => write!(out, "{:02}", datetime.minute())?,
+            'S' => write!(out, "{:02}", datetime.second())?,
+            'j' => write!(out, "{:03}", datetime.ordinal())?,
+            'a' => out.write_str(WEEKDAYS[datetime.weekday().num_days_from_monday() as usize])?,
+            'b' => out.write_str(MONTHS[datetime.month0() as usize])?,
+            '%' => out.write_char('%')?,
+            _ => return Err(fmt::Error),
+        }
+    }
+    Ok(())
+}
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Set the date and time from a parsed text input.
+    ///
+    /// A time of day is written in the 12-hour or 24-hour format of the input,
+    /// keeping the date. A relative adjustment is applied with
+    /// [`adjust_by()`](Ds1307::adjust_by).
+    pub fn set_time_input(&mut self, input: &TimeInput) -> Result<(), Error<E>> {
+        match *input {
+            TimeInput::DateTime(datetime) => self.set_datetime(&datetime),
+            TimeInput::Time {
+                hours,
+                minutes,
+                seconds,
+            } => {
+                if minutes > 59 || seconds > 59 {
+                    return Err(Error::InvalidInputData);
+                }
+                let hour = self.get_hours_register_value(hours)?;
+                let ch_flag = self.read_register(Register::SECONDS)? & BitFlags::CH;
+                let payload = [
+                    Register::SECONDS,
+                    decimal_to_packed_bcd(seconds) | ch_flag,
+                    decimal_to_packed_bcd(minutes),
+                    hour,
+                ];
+                self.write_registers(&payload)
+            }
+            TimeInput::Adjust(seconds) => self.adjust_by(seconds),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::bcd::{decimal_to_packed_bcd, is_24h_format, is_am, packed_bcd_to_decimal};
+use crate::register_access::ADDR;
+use crate::{BitFlags, Ds1307, Error, Register};
+use embedded_hal::i2c::I2c;
+
+/// Number of seconds the clock may have advanced between writing the
+/// timekeeping registers and reading them back.
+const TICK_TOLERANCE: usize = 2;
+
+/// Number of write verification failures per register group.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct VerifyFailures {
+    /// Failures writing the date and time registers.
+    pub timekeeping: u32,
+    /// Failures writing the control register.
+    pub control: u32,
+    /// Failures writing the user RAM.
+    pub ram: u32,
+}
+
+impl VerifyFailures {
+    /// Total number of verification failures.
+    pub fn total(&self) -> u32 {
+        self.timekeeping
+            .saturating_add(self.control)
+            .saturating_add(self.ram)
+    }
+}
+
+impl<I2C> Ds1307<I2C> {
+    /// Enable or disable write verification (disabled by default).
+    ///
+    /// When enabled, every register write is read back and compared. Writes
+    /// to the date and time registers allow for the clock having advanced up
+    /// to 2 seconds in between. Writing only some of them additionally reads
+    /// the date and time before writing. On mismatch, `Error::VerifyFailed` is
+    /// returned and the corresponding failure counter is incremented.
+    pub fn set_write_verification(&mut self, enabled: bool) {
+        self.verify_writes = enabled;
+    }
+
+    /// Get whether write verification is enabled.
+    pub fn write_verification(&self) -> bool {
+        self.verify_writes
+    }
+
+    /// Get the number of write verification failures.
+    pub fn verification_failures(&self) -> VerifyFailures {
+        self.verify_failures
+    }
+
+    /// Reset the write verification failure counters.
+    pub fn reset_verification_failures(&mut self) {
+        self.verify_failures = VerifyFailures::default();
+    }
+}
+
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Write consecutive registers, read them back and compare them.
+    ///
+    /// For partial writes of the date and time registers the whole date and
+    /// time is read before writing, so that carries from the registers not
+    /// written into the written ones can be reproduced.
+    pub(crate) fn write_verified(&mut self, payload: &[u8]) -> Result<(), Error<E>> {
+        let (start, data) = match payload.split_first() {
+            Some((start, data)) if !data.is_empty() => (*start, data),
+            _ => return self.i2c.write(ADDR, payload).map_err(Error::I2C),
+        };
+        let range = usize::from(start)..usize::from(start) + data.len();
+        let mut expected = [0; 64];
+        if start < Register::SQWOUT && (range.start > 0 || range.end < 7) {
+            self.read_registers(Register::SECONDS, &mut expected[..range.end.max(7)])?;
+        }
+        expected[range.clone()].copy_from_slice(data);
+        self.i2c.write(ADDR, payload).map_err(Error::I2C)?;
+        let mut read_back = [0; 64];
+        let matches = if start >= Register::SQWOUT {
+            let read_back = &mut read_back[..data.len()];
+            self.read_registers(start, read_back)?;
+            read_back == data
+        } else {
+            self.read_registers(Register::SECONDS, &mut read_back[..range.end.max(7)])?;
+            (0..=TICK_TOLERANCE).any(|_| {
+                let matches = expected[range.clone()] == read_back[range.clone()];
+                tick(&mut expected);
+                matches
+            })
+        };
+        if matches {
+            return Ok(());
+        }
+        let counter = if start >= Register::RAM_BEGIN {
+            &mut self.verify_failures.ram
+        } else if start == Register::SQWOUT {
+            &mut self.verify_failures.control
+        } else {
+            &mut self.verify_failures.timekeeping
+        };
+        *counter = counter.saturating_add(1);
+        Err(Error::VerifyFailed)
+    }
+}
+
+/// Advance the date and time registers by one second like the device does.
+fn tick(registers: &mut [u8]) {
+    let ch_flag = registers[0] & BitFlags::CH;
+    let second = packed_bcd_to_decimal(registers[0] & !BitFlags::CH) + 1;
+    if second < 60 {
+        registers[0] = ch_flag | decimal_to_packed_bcd(second);
+        return;
+    }
+    registers[0] = ch_flag;
+    let minute = packed_bcd_to_decimal(registers[1]) + 1;
+    if minute < 60 {
+        registers[1] = decimal_to_packed_bcd(minute);
+        return;
+    }
+    registers[1] = 0;
+    if !tick_hour(&mut registers[2]) {
+        return;
+    }
+    registers[3] = registers[3] % 7 + 1;
+    let year = packed_bcd_to_decimal(registers[6]);
+    let month = packed_bcd_to_decimal(registers[5]);
+    let day = packed_bcd_to_decimal(registers[4]) + 1;
+    if day <= days_in_month(year, month) {
+        registers[4] = decimal_to_packed_bcd(day);
+        return;
+    }
+    registers[4] = 1;
+    if month < 12 {
+        registers[5] = decimal_to_packed_bcd(month + 1);
+        return;
+    }
+    registers[5] = 1;
+    registers[6] = decimal_to_packed_bcd((year + 1) % 100);
+}
+
+/// Advance the hours register by one hour. Returns whether the day changed.
+fn tick_hour(hours: &mut u8) -> bool {
+    if is_24h_format(*hours) {
+        let hour = packed_bcd_to_decimal(*hours) + 1;
+        *hours = decimal_to_packed_bcd(hour % 24);
+        return hour == 24;
+    }
+    let flags = *hours & (BitFlags::H24_H12 | BitFlags::AM_PM);
+    match packed_bcd_to_decimal(*hours & !flags) {
+        11 => {
+            *hours = (flags ^ BitFlags::AM_PM) | decimal_to_packed_bcd(12);
+            !is_am(flags)
+        }
+        12 => {
+            *hours = flags | 1;
+            false
+        }
+        hour => {
+            *hours = flags | decimal_to_packed_bcd(hour + 1);
+            false
+        }
+    }
+}
+
+fn days_in_month(year: u8, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticked(mut registers: [u8; 7]) -> [u8; 7] {
+        tick(&mut registers);
+        registers
+    }
+
+    #[test]
+    fn can_tick_seconds() {
+        let registers = [0x58, 0x59, 0x23, 2, 0x13, 0x08, 0x18];
+        assert_eq!([0x59, 0x59, 0x23, 2, 0x13, 0x08, 0x18], ticked(registers));
+        let registers = [0x80 | 0x09, 0, 0, 1, 1, 1, 0];
+        assert_eq!([0x80 | 0x10, 0, 0, 1, 1, 1, 0], ticked(registers));
+    }
+
+    #[test]
+    fn can_tick_into_next_day() {
+        let registers = [0x59, 0x59, 0x23, 7, 0x31, 0x08, 0x18];
+        assert_eq!([0, 0, 0, 1, 0x01, 0x09, 0x18], ticked(registers));
+    }
+
+    #[test]
+    fn can_tick_into_leap_day() {
+        let registers = [0x59, 0x59, 0x23, 4, 0x28, 0x02, 0x24];
+        assert_eq!([0, 0, 0, 5, 0x29, 0x02, 0x24], ticked(registers));
+        let registers = [0x59, 0x59, 0x23, 4, 0x28, 0x02, 0x23];
+        assert_eq!([0, 0, 0, 5, 0x01, 0x03, 0x23], ticked(registers));
+    }
+
+    #[test]
+    fn can_tick_into_next_year() {
+        let registers = [0x59, 0x59, 0x23, 1, 0x31, 0x12, 0x99];
+        assert_eq!([0, 0, 0, 2, 0x01, 0x01, 0x00], ticked(registers));
+    }
+
+    #[test]
+    fn can_tick_12h_hours() {
+        // 11:59:59 AM -> 12:00:00 PM
+        let registers = [0x59, 0x59, 0b0101_0001, 1, 0x01, 0x01, 0x18];
+        assert_eq!([0, 0, 0b0111_0010, 1, 0x01, 0x01, 0x18], ticked(registers));
+        // 12:59:59 PM -> 1:00:00 PM
+        let registers = [0x59, 0x59, 0b0111_0010, 1, 0x01, 0x01, 0x18];
+        assert_eq!([0, 0, 0b0110_0001, 1, 0x01, 0x01, 0x18], ticked(registers));
+        // 11:59:59 PM -> 12:00:00 AM next day
+        let registers = [0x59, 0x59, 0b0111_0001, 1, 0x01, 0x01, 0x18];
+        assert_eq!([0, 0, 0b0101_0010, 2, 0x02, 0x01, 0x18], ticked(registers));
+    }
+}
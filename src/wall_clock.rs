@@ -0,0 +1,116 @@
+use crate::{DateTimeAccess, Ds1307, Error, NaiveDateTime};
+use chrono::TimeDelta;
+use embassy_time::{Duration, Instant, Timer};
+use embedded_hal::i2c::I2c;
+
+/// Wall-clock time source backed by a DS1307 and the Embassy monotonic timer.
+///
+/// The RTC is read once on creation and the result is paired with an
+/// [`embassy_time::Instant`]. Afterwards the current time is derived from the
+/// monotonic timer and the RTC is only read again at a configurable interval
+/// to bound the drift between both clocks.
+#[derive(Debug)]
+pub struct WallClock<I2C> {
+    rtc: Ds1307<I2C>,
+    resync_interval: Duration,
+    anchor_datetime: NaiveDateTime,
+    anchor_instant: Instant,
+}
+
+impl<I2C, E> WallClock<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new instance.
+    ///
+    /// Reads the current date and time from the RTC. Afterwards the RTC
+    /// will be read again whenever `resync_interval` has passed.
+    pub fn new(mut rtc: Ds1307<I2C>, resync_interval: Duration) -> Result<Self, Error<E>> {
+        let anchor_datetime = rtc.datetime()?;
+        Ok(WallClock {
+            rtc,
+            resync_interval,
+            anchor_datetime,
+            anchor_instant: Instant::now(),
+        })
+    }
+
+    /// Destroy the wall clock instance, return the DS1307 driver instance.
+    pub fn destroy(self) -> Ds1307<I2C> {
+        self.rtc
+    }
+
+    /// Get the current date and time.
+    ///
+    /// The RTC will only be accessed if the resync interval has passed
+    /// since it was last read.
+    pub fn now(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        let now = Instant::now();
+        if now.saturating_duration_since(self.anchor_instant) >= self.resync_interval {
+            self.resync()?;
+            return Ok(self.anchor_datetime);
+        }
+        Ok(self.to_datetime(now))
+    }
+
+    /// Read the date and time from the RTC again immediately.
+    ///
+    /// The RTC only provides whole seconds, so the current estimate is kept
+    /// as long as it lies within the second read from the RTC. This avoids
+    /// stepping backwards by the truncated fraction of a second on every resync.
+    pub fn resync(&mut self) -> Result<(), Error<E>> {
+        let reading = self.rtc.datetime()?;
+        let now = Instant::now();
+        let next_second = reading
+            .checked_add_signed(TimeDelta::seconds(1))
+            .unwrap_or(reading);
+        self.anchor_datetime = self.to_datetime(now).clamp(reading, next_second);
+        self.anchor_instant = now;
+        Ok(())
+    }
+
+    /// Set the date and time on the RTC and use it as new reference.
+    pub fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Error<E>> {
+        self.rtc.set_datetime(datetime)?;
+        self.anchor_datetime = *datetime;
+        self.anchor_instant = Instant::now();
+        Ok(())
+    }
+
+    /// Convert a monotonic timer instant into a wall-clock date and time.
+    pub fn to_datetime(&self, instant: Instant) -> NaiveDateTime {
+        let delta = if instant >= self.anchor_instant {
+            TimeDelta::microseconds(instant.duration_since(self.anchor_instant).as_micros() as i64)
+        } else {
+            -TimeDelta::microseconds(self.anchor_instant.duration_since(instant).as_micros() as i64)
+        };
+        self.anchor_datetime
+            .checked_add_signed(delta)
+            .unwrap_or(self.anchor_datetime)
+    }
+
+    /// Convert a wall-clock date and time into a monotonic timer instant.
+    ///
+    /// Returns `None` if the date and time cannot be represented as an
+    /// instant, for example if it lies before the start of the timer.
+    pub fn to_instant(&self, datetime: &NaiveDateTime) -> Option<Instant> {
+        let micros = datetime
+            .signed_duration_since(self.anchor_datetime)
+            .num_microseconds()?;
+        let delta = Duration::from_micros(micros.unsigned_abs());
+        if micros >= 0 {
+            self.anchor_instant.checked_add(delta)
+        } else {
+            self.anchor_instant.checked_sub(delta)
+        }
+    }
+
+    /// Wait until the given wall-clock date and time is reached.
+    ///
+    /// Returns immediately if the date and time is already in the past.
+    pub async fn wait_until(&self, datetime: &NaiveDateTime) {
+        if let Some(instant) = self.to_instant(datetime) {
+            Timer::at(instant).await;
+        }
+    }
+}
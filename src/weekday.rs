@@ -0,0 +1,95 @@
+use crate::Ds1307;
+#[cfg(feature = "chrono")]
+use crate::{bcd::packed_bcd_to_decimal, Error, Register};
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDate};
+#[cfg(feature = "chrono")]
+use embedded_hal::i2c::I2c;
+
+/// Numbering of the days of the week in the day-of-week register
+///
+/// The device only increments the day-of-week register at midnight, the
+/// meaning of its values [1-7] is up to the user. The convention is used
+/// whenever the driver computes the day of the week from a date. The values
+/// read with `weekday()` and written with `set_weekday()` follow it as well.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum WeekdayConvention {
+    /// Sunday is 1, Saturday is 7.
+    #[default]
+    SundayOne,
+    /// Monday is 1, Sunday is 7.
+    MondayOne,
+}
+
+impl WeekdayConvention {
+    /// Day-of-week register value for the number of days since Sunday [0-6].
+    #[cfg_attr(
+        not(any(feature = "chrono", feature = "time", feature = "jiff")),
+        allow(dead_code)
+    )]
+    pub(crate) fn register_value(self, days_from_sunday: u8) -> u8 {
+        match self {
+            WeekdayConvention::SundayOne => days_from_sunday + 1,
+            WeekdayConvention::MondayOne => (days_from_sunday + 6) % 7 + 1,
+        }
+    }
+}
+
+impl<I2C> Ds1307<I2C> {
+    /// Get the day-of-week numbering convention in use.
+    pub fn weekday_convention(&self) -> WeekdayConvention {
+        self.weekday_convention
+    }
+
+    /// Set the day-of-week numbering convention.
+    ///
+    /// This does not change the device contents.
+    pub fn set_weekday_convention(&mut self, convention: WeekdayConvention) {
+        self.weekday_convention = convention;
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<I2C, E> Ds1307<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read whether the day-of-week register matches the date.
+    ///
+    /// Will return an `Error::InvalidInputData` if the device contains an invalid date.
+    pub fn weekday_consistent(&mut self) -> Result<bool, Error<E>> {
+        let (weekday, expected) = self.read_weekday_and_expected()?;
+        Ok(weekday == expected)
+    }
+
+    /// Set the day-of-week register according to the date if it does not match.
+    ///
+    /// Returns whether the register was changed.
+    ///
+    /// Will return an `Error::InvalidInputData` if the device contains an invalid date.
+    pub fn repair_weekday(&mut self) -> Result<bool, Error<E>> {
+        let (weekday, expected) = self.read_weekday_and_expected()?;
+        if weekday == expected {
+            return Ok(false);
+        }
+        self.write_register(Register::DOW, expected)?;
+        Ok(true)
+    }
+
+    fn read_weekday_and_expected(&mut self) -> Result<(u8, u8), Error<E>> {
+        let mut data = [0; 4];
+        self.read_registers(Register::DOW, &mut data)?;
+        let year = 2000 + i32::from(packed_bcd_to_decimal(data[3]));
+        let month = packed_bcd_to_decimal(data[2]);
+        let day = packed_bcd_to_decimal(data[1]);
+        let date = NaiveDate::from_ymd_opt(year, month.into(), day.into())
+            .ok_or(Error::InvalidInputData)?;
+        let expected = self.weekday_register_value(&date);
+        Ok((data[0], expected))
+    }
+
+    pub(crate) fn weekday_register_value(&self, date: &NaiveDate) -> u8 {
+        let days_from_sunday = date.weekday().num_days_from_sunday() as u8;
+        self.weekday_convention.register_value(days_from_sunday)
+    }
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{Error, TimeDelta};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{destroy, new, read_datetime_registers, Register, ADDR};
+
+fn read_seconds(seconds: u8) -> I2cTrans {
+    I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![seconds])
+}
+
+#[test]
+fn can_adjust_across_year_boundary() {
+    let mut rtc = new(&[
+        read_datetime_registers([0x30, 0x59, 0x23, 7, 0x31, 0x12, 0x23]),
+        read_seconds(0x30),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x00, 0x01, 0x00, 1, 0x01, 0x01, 0x24],
+        ),
+    ]);
+    rtc.adjust_by(90).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn can_adjust_backwards() {
+    let mut rtc = new(&[
+        read_datetime_registers([0x10, 0x00, 0x00, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x10),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x50, 0x59, 0x23, 1, 0x31, 0x12, 0x23],
+        ),
+    ]);
+    rtc.adjust(TimeDelta::seconds(-20)).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn writes_only_changed_registers() {
+    let mut rtc = new(&[
+        read_datetime_registers([0x10, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x10),
+        I2cTrans::write(ADDR, vec![Register::MINUTES, 0x22]),
+    ]);
+    rtc.adjust(TimeDelta::minutes(2)).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn zero_adjustment_writes_nothing() {
+    let mut rtc = new(&[
+        read_datetime_registers([0x10, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x10),
+    ]);
+    rtc.adjust_by(0).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn preserves_clock_halt_bit() {
+    let mut rtc = new(&[
+        read_datetime_registers([0x80 | 0x10, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x80 | 0x10),
+        I2cTrans::write(ADDR, vec![Register::SECONDS, 0x80 | 0x15]),
+    ]);
+    rtc.adjust_by(5).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn preserves_12h_mode() {
+    // 11:59:58 PM -> 12:00:03 AM next day
+    let mut rtc = new(&[
+        read_datetime_registers([0x58, 0x59, 0b0111_0001, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x58),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x03, 0x00, 0b0101_0010, 3, 0x02],
+        ),
+    ]);
+    rtc.adjust_by(5).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn preserves_weekday_numbering() {
+    // Monday as 1, one week and one day back
+    let mut rtc = new(&[
+        read_datetime_registers([0x00, 0x00, 0x12, 1, 0x12, 0x02, 0x24]),
+        read_seconds(0x00),
+        I2cTrans::write(ADDR, vec![Register::DOW, 7, 0x04]),
+    ]);
+    rtc.adjust(TimeDelta::days(-8)).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn retries_if_second_boundary_passed() {
+    let mut rtc = new(&[
+        read_datetime_registers([0x10, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x11),
+        read_datetime_registers([0x11, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x11),
+        I2cTrans::write(ADDR, vec![Register::SECONDS, 0x16]),
+    ]);
+    rtc.adjust_by(5).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn recomputes_after_last_attempt() {
+    let mut rtc = new(&[
+        read_datetime_registers([0x10, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x11),
+        read_datetime_registers([0x11, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x12),
+        read_datetime_registers([0x12, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        read_seconds(0x13),
+        read_datetime_registers([0x13, 0x20, 0x13, 2, 0x01, 0x01, 0x24]),
+        I2cTrans::write(ADDR, vec![Register::SECONDS, 0x18]),
+    ]);
+    rtc.adjust_by(5).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn adjusting_out_of_range_is_error() {
+    let mut rtc = new(&[read_datetime_registers([
+        0x10, 0x00, 0x00, 7, 0x01, 0x01, 0x00,
+    ])]);
+    match rtc.adjust_by(-20) {
+        Err(Error::InvalidInputData) => (),
+        _ => panic!("InvalidInputData error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn adjusting_invalid_date_is_error() {
+    let mut rtc = new(&[read_datetime_registers([
+        0x10, 0x00, 0x00, 7, 0x31, 0x02, 0x24,
+    ])]);
+    match rtc.adjust_by(1) {
+        Err(Error::InvalidInputData) => (),
+        _ => panic!("InvalidInputData error not returned."),
+    }
+    destroy(rtc);
+}
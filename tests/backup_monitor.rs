@@ -0,0 +1,149 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{BackupMonitor, BackupStatus, NaiveDateTime, TimeDelta};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{
+    crc8, datetime, destroy, new, read_datetime, read_datetime_registers, read_ram,
+    rtc_epoch_seconds, write_ram, Register, ADDR, OFFSET,
+};
+
+fn alive() -> NaiveDateTime {
+    datetime(2018, 8, 13, 23, 0, 10)
+}
+
+fn monitor() -> BackupMonitor {
+    BackupMonitor::new(OFFSET, TimeDelta::minutes(5))
+}
+
+fn heartbeat(alive: &NaiveDateTime) -> Vec<u8> {
+    let mut data = vec![0xB4, 0x77];
+    data.extend_from_slice(&rtc_epoch_seconds(alive).to_le_bytes());
+    data.push(crc8(&data));
+    data
+}
+
+fn read_running(seconds: u8) -> I2cTrans {
+    I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![seconds])
+}
+
+#[test]
+fn can_write_heartbeat() {
+    let mut rtc = new(&[
+        read_datetime(&alive()),
+        write_ram(OFFSET, heartbeat(&alive())),
+    ]);
+    monitor().heartbeat(&mut rtc).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn can_read_last_alive() {
+    let mut rtc = new(&[read_ram(OFFSET, heartbeat(&alive()))]);
+    assert_eq!(Some(alive()), monitor().last_alive(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn missing_signature_is_first_power_up() {
+    let mut rtc = new(&[read_ram(OFFSET, vec![0; 7])]);
+    assert_eq!(
+        BackupStatus::FirstPowerUp,
+        monitor().backup_status(&mut rtc, None).unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn corrupted_data_is_first_power_up() {
+    let mut data = heartbeat(&alive());
+    data[3] ^= 1;
+    let mut rtc = new(&[read_ram(OFFSET, data)]);
+    assert_eq!(
+        BackupStatus::FirstPowerUp,
+        monitor().backup_status(&mut rtc, None).unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn halted_clock_is_failure() {
+    let mut rtc = new(&[read_ram(OFFSET, heartbeat(&alive())), read_running(0x80)]);
+    assert_eq!(
+        BackupStatus::BatteryLikelyFailed,
+        monitor().backup_status(&mut rtc, None).unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn time_before_last_alive_is_failure() {
+    let mut rtc = new(&[
+        read_ram(OFFSET, heartbeat(&alive())),
+        read_running(0x00),
+        read_datetime(&datetime(2018, 8, 13, 1, 0, 0)),
+    ]);
+    assert_eq!(
+        BackupStatus::BatteryLikelyFailed,
+        monitor().backup_status(&mut rtc, None).unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_date_is_failure() {
+    let mut rtc = new(&[
+        read_ram(OFFSET, heartbeat(&alive())),
+        read_running(0x00),
+        read_datetime_registers([0x00, 0x00, 0x23, 2, 0x31, 0x02, 0x18]),
+    ]);
+    assert_eq!(
+        BackupStatus::BatteryLikelyFailed,
+        monitor().backup_status(&mut rtc, None).unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn time_after_last_alive_is_ok() {
+    let mut rtc = new(&[
+        read_ram(OFFSET, heartbeat(&alive())),
+        read_running(0x30),
+        read_datetime(&datetime(2018, 8, 13, 23, 0, 30)),
+    ]);
+    assert_eq!(
+        BackupStatus::BatteryOk,
+        monitor().backup_status(&mut rtc, None).unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn lagging_behind_reference_is_failure() {
+    let mut rtc = new(&[
+        read_ram(OFFSET, heartbeat(&alive())),
+        read_running(0x30),
+        read_datetime(&datetime(2018, 8, 13, 23, 0, 30)),
+    ]);
+    let reference = datetime(2018, 8, 13, 23, 0, 30) + TimeDelta::minutes(10);
+    assert_eq!(
+        BackupStatus::BatteryLikelyFailed,
+        monitor().backup_status(&mut rtc, Some(reference)).unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn matching_reference_is_ok() {
+    let mut rtc = new(&[
+        read_ram(OFFSET, heartbeat(&alive())),
+        read_running(0x30),
+        read_datetime(&datetime(2018, 8, 13, 23, 0, 30)),
+    ]);
+    let reference = datetime(2018, 8, 13, 23, 0, 30) + TimeDelta::minutes(2);
+    assert_eq!(
+        BackupStatus::BatteryOk,
+        monitor().backup_status(&mut rtc, Some(reference)).unwrap()
+    );
+    destroy(rtc);
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{BootLedger, Error, NaiveDateTime, TimeDelta};
+mod common;
+use crate::common::{
+    crc8, datetime, destroy, new, read_datetime, read_ram, rtc_epoch_seconds, write_ram, OFFSET,
+};
+
+fn ledger(boot_count: u32, heartbeat: Option<NaiveDateTime>, powered: u32) -> Vec<u8> {
+    let heartbeat = heartbeat.map_or(0, |hb| rtc_epoch_seconds(&hb));
+    let mut data = Vec::new();
+    data.extend_from_slice(&boot_count.to_le_bytes());
+    data.extend_from_slice(&heartbeat.to_le_bytes());
+    data.extend_from_slice(&powered.to_le_bytes());
+    data.push(crc8(&data));
+    data
+}
+
+#[test]
+fn first_boot_resets_ledger() {
+    let mut rtc = new(&[
+        read_ram(OFFSET, vec![0; 13]),
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 58)),
+        write_ram(
+            OFFSET,
+            ledger(1, Some(datetime(2018, 8, 13, 23, 59, 58)), 0),
+        ),
+    ]);
+    let ledger = BootLedger::record_boot(&mut rtc, OFFSET).unwrap();
+    assert!(ledger.was_reset());
+    assert_eq!(1, ledger.boot_count());
+    assert_eq!(None, ledger.last_downtime());
+    assert_eq!(
+        Some(datetime(2018, 8, 13, 23, 59, 58)),
+        ledger.last_heartbeat()
+    );
+    assert_eq!(TimeDelta::zero(), ledger.powered_time());
+    destroy(rtc);
+}
+
+#[test]
+fn corrupted_ledger_is_reset() {
+    let mut data = ledger(5, Some(datetime(2018, 8, 13, 22, 0, 0)), 100);
+    data[0] ^= 1;
+    let mut rtc = new(&[
+        read_ram(OFFSET, data),
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 58)),
+        write_ram(
+            OFFSET,
+            ledger(1, Some(datetime(2018, 8, 13, 23, 59, 58)), 0),
+        ),
+    ]);
+    let ledger = BootLedger::record_boot(&mut rtc, OFFSET).unwrap();
+    assert!(ledger.was_reset());
+    assert_eq!(1, ledger.boot_count());
+    assert_eq!(None, ledger.last_downtime());
+    destroy(rtc);
+}
+
+#[test]
+fn can_record_boot() {
+    let mut rtc = new(&[
+        read_ram(
+            OFFSET,
+            ledger(5, Some(datetime(2018, 8, 13, 22, 0, 0)), 100),
+        ),
+        read_datetime(&datetime(2018, 8, 13, 23, 30, 0)),
+        write_ram(
+            OFFSET,
+            ledger(6, Some(datetime(2018, 8, 13, 23, 30, 0)), 100),
+        ),
+    ]);
+    let ledger = BootLedger::record_boot(&mut rtc, OFFSET).unwrap();
+    assert!(!ledger.was_reset());
+    assert_eq!(6, ledger.boot_count());
+    assert_eq!(Some(TimeDelta::seconds(5400)), ledger.last_downtime());
+    assert_eq!(TimeDelta::seconds(100), ledger.powered_time());
+    destroy(rtc);
+}
+
+#[test]
+fn downtime_is_unknown_if_clock_went_backwards() {
+    let mut rtc = new(&[
+        read_ram(
+            OFFSET,
+            ledger(5, Some(datetime(2018, 8, 13, 22, 0, 0)), 100),
+        ),
+        read_datetime(&datetime(2018, 8, 13, 21, 0, 0)),
+        write_ram(
+            OFFSET,
+            ledger(6, Some(datetime(2018, 8, 13, 21, 0, 0)), 100),
+        ),
+    ]);
+    let ledger = BootLedger::record_boot(&mut rtc, OFFSET).unwrap();
+    assert_eq!(None, ledger.last_downtime());
+    destroy(rtc);
+}
+
+#[test]
+fn heartbeat_accumulates_powered_time() {
+    let mut rtc = new(&[
+        read_ram(
+            OFFSET,
+            ledger(5, Some(datetime(2018, 8, 13, 22, 0, 0)), 100),
+        ),
+        read_datetime(&datetime(2018, 8, 13, 23, 30, 0)),
+        write_ram(
+            OFFSET,
+            ledger(6, Some(datetime(2018, 8, 13, 23, 30, 0)), 100),
+        ),
+        read_datetime(&datetime(2018, 8, 13, 23, 31, 0)),
+        write_ram(
+            OFFSET,
+            ledger(6, Some(datetime(2018, 8, 13, 23, 31, 0)), 160),
+        ),
+    ]);
+    let mut ledger = BootLedger::record_boot(&mut rtc, OFFSET).unwrap();
+    ledger.heartbeat(&mut rtc).unwrap();
+    assert_eq!(
+        Some(datetime(2018, 8, 13, 23, 31, 0)),
+        ledger.last_heartbeat()
+    );
+    assert_eq!(TimeDelta::seconds(160), ledger.powered_time());
+    assert_eq!(Some(TimeDelta::seconds(5400)), ledger.last_downtime());
+    destroy(rtc);
+}
+
+#[test]
+fn ledger_must_fit_in_ram() {
+    let mut rtc = new(&[]);
+    assert_invalid_input_data!(BootLedger::record_boot(&mut rtc, 44));
+    destroy(rtc);
+}
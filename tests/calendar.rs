@@ -0,0 +1,104 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{CalendarView, Error, NaiveDate, Weekday};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{destroy, new, Register, ADDR};
+
+fn view(year: i32, month: u32, day: u32) -> CalendarView {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn can_read_calendar_at_once() {
+    let mut rtc = new(&[I2cTrans::write_read(
+        ADDR,
+        vec![Register::SECONDS],
+        vec![0x58, 0x59, 0x23, 2, 0x31, 0x12, 0x18],
+    )]);
+    let calendar = rtc.calendar().unwrap();
+    let expected = NaiveDate::from_ymd_opt(2018, 12, 31).unwrap();
+    assert_eq!(
+        expected.and_hms_opt(23, 59, 58).unwrap(),
+        calendar.datetime()
+    );
+    assert_eq!(expected, calendar.date());
+    assert_eq!(Weekday::Mon, calendar.weekday());
+    assert_eq!(365, calendar.day_of_year());
+    let week = calendar.iso_week();
+    assert_eq!((2019, 1), (week.year(), week.week()));
+    assert!(calendar.is_last_day_of_month());
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_date_is_reported() {
+    let mut rtc = new(&[I2cTrans::write_read(
+        ADDR,
+        vec![Register::SECONDS],
+        vec![0x00, 0x00, 0x12, 2, 0x31, 0x02, 0x18],
+    )]);
+    match rtc.calendar() {
+        Err(Error::InvalidInputData) => (),
+        _ => panic!("InvalidInputData error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn can_get_day_of_year() {
+    assert_eq!(1, view(2024, 1, 1).day_of_year());
+    assert_eq!(60, view(2024, 2, 29).day_of_year());
+    assert_eq!(366, view(2024, 12, 31).day_of_year());
+    assert_eq!(60, view(2023, 3, 1).day_of_year());
+}
+
+#[test]
+fn can_get_iso_week() {
+    let week = view(2021, 1, 3).iso_week();
+    assert_eq!((2020, 53), (week.year(), week.week()));
+    let week = view(2021, 1, 4).iso_week();
+    assert_eq!((2021, 1), (week.year(), week.week()));
+    let week = view(2024, 6, 15).iso_week();
+    assert_eq!((2024, 24), (week.year(), week.week()));
+}
+
+#[test]
+fn can_get_month_boundaries() {
+    let calendar = view(2024, 2, 10);
+    assert_eq!(29, calendar.days_in_month());
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+        calendar.first_day_of_month()
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+        calendar.last_day_of_month()
+    );
+    assert_eq!(19, calendar.days_until_month_end());
+    assert!(!calendar.is_first_day_of_month());
+    assert!(!calendar.is_last_day_of_month());
+    assert_eq!(28, view(2023, 2, 1).days_in_month());
+    assert_eq!(30, view(2023, 4, 1).days_in_month());
+    assert_eq!(31, view(2023, 12, 1).days_in_month());
+}
+
+#[test]
+fn can_detect_first_and_last_day_of_month() {
+    assert!(view(2023, 4, 1).is_first_day_of_month());
+    assert!(view(2023, 4, 30).is_last_day_of_month());
+    assert!(view(2023, 2, 28).is_last_day_of_month());
+    assert!(!view(2024, 2, 28).is_last_day_of_month());
+    assert_eq!(0, view(2023, 12, 31).days_until_month_end());
+}
+
+#[test]
+fn can_detect_leap_year() {
+    assert!(view(2024, 1, 1).is_leap_year());
+    assert!(view(2000, 1, 1).is_leap_year());
+    assert!(!view(2023, 1, 1).is_leap_year());
+}
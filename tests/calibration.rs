@@ -0,0 +1,60 @@
+use ds1307::{Calibration, SqwOutRate};
+
+#[test]
+fn zero_periods_is_invalid() {
+    assert_eq!(None, Calibration::new(SqwOutRate::Hz1, 0, 100));
+}
+
+#[test]
+fn exact_clock_has_no_error() {
+    let calibration = Calibration::new(SqwOutRate::Hz1, 10, 80_000_000).unwrap();
+    assert_eq!(8_000_000, calibration.measured_hz());
+    assert_eq!(Some(0), calibration.error_ppm(8_000_000));
+}
+
+#[test]
+fn fast_clock_has_positive_error() {
+    let calibration = Calibration::new(SqwOutRate::Khz32_768, 32768, 16_000_800).unwrap();
+    assert_eq!(16_000_800, calibration.measured_hz());
+    assert_eq!(Some(50), calibration.error_ppm(16_000_000));
+}
+
+#[test]
+fn slow_clock_has_negative_error() {
+    // 4096 periods at 4.096 kHz = 1 s
+    let calibration = Calibration::new(SqwOutRate::Khz4_096, 4096, 999_900).unwrap();
+    assert_eq!(999_900, calibration.measured_hz());
+    assert_eq!(Some(-100), calibration.error_ppm(1_000_000));
+}
+
+#[test]
+fn results_are_rounded() {
+    // 3 periods at 1 Hz: 10 ticks = 3.33 Hz
+    let calibration = Calibration::new(SqwOutRate::Hz1, 3, 10).unwrap();
+    assert_eq!(3, calibration.measured_hz());
+    // 3.33 Hz vs 3 Hz nominal = +111111.1 ppm
+    assert_eq!(Some(111_111), calibration.error_ppm(3));
+    // 3.33 Hz vs 4 Hz nominal = -166666.7 ppm
+    assert_eq!(Some(-166_667), calibration.error_ppm(4));
+}
+
+#[test]
+fn zero_nominal_frequency_is_invalid() {
+    let calibration = Calibration::new(SqwOutRate::Hz1, 1, 1).unwrap();
+    assert_eq!(None, calibration.error_ppm(0));
+}
+
+#[test]
+fn large_errors_saturate() {
+    let calibration = Calibration::new(SqwOutRate::Khz32_768, 1, u32::MAX).unwrap();
+    assert_eq!(Some(i32::MAX), calibration.error_ppm(1));
+    assert_eq!(u32::MAX, calibration.measured_hz());
+}
+
+#[cfg(feature = "fugit")]
+#[test]
+fn can_get_measured_rate() {
+    use fugit::RateExtU32;
+    let calibration = Calibration::new(SqwOutRate::Hz1, 2, 64).unwrap();
+    assert_eq!(32.Hz::<1, 1>(), calibration.measured_rate());
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{ClockSync, NaiveDateTime, StartupPolicy, SyncError, SystemClock, TimeDelta};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{datetime, destroy, new, read_datetime, Register, ADDR};
+
+#[derive(Debug, PartialEq)]
+struct ClockError;
+
+struct FakeClock {
+    now: NaiveDateTime,
+    set_to: Option<NaiveDateTime>,
+    fail: bool,
+}
+
+impl FakeClock {
+    fn new(now: NaiveDateTime) -> Self {
+        FakeClock {
+            now,
+            set_to: None,
+            fail: false,
+        }
+    }
+}
+
+impl SystemClock for FakeClock {
+    type Error = ClockError;
+
+    fn now(&mut self) -> Result<NaiveDateTime, Self::Error> {
+        if self.fail {
+            return Err(ClockError);
+        }
+        Ok(self.now)
+    }
+
+    fn set(&mut self, datetime: &NaiveDateTime) -> Result<(), Self::Error> {
+        if self.fail {
+            return Err(ClockError);
+        }
+        self.set_to = Some(*datetime);
+        Ok(())
+    }
+}
+
+macro_rules! startup_test {
+    ($name:ident, $policy:expr, $system:expr, $updated:expr) => {
+        #[test]
+        fn $name() {
+            let mut rtc = new(&[read_datetime(&datetime(2018, 8, 13, 12, 0, 0))]);
+            let mut clock = FakeClock::new($system);
+            let report = ClockSync::new($policy)
+                .rtc_to_system(&mut rtc, &mut clock)
+                .unwrap();
+            assert_eq!(datetime(2018, 8, 13, 12, 0, 0), report.rtc);
+            assert_eq!($system, report.system);
+            assert_eq!(datetime(2018, 8, 13, 12, 0, 0) - $system, report.drift());
+            assert_eq!($updated, report.system_updated);
+            assert!(!report.rtc_updated);
+            let expected = if $updated {
+                Some(datetime(2018, 8, 13, 12, 0, 0))
+            } else {
+                None
+            };
+            assert_eq!(expected, clock.set_to);
+            destroy(rtc);
+        }
+    };
+}
+
+startup_test!(
+    never,
+    StartupPolicy::Never,
+    datetime(2018, 8, 13, 11, 0, 0),
+    false
+);
+startup_test!(
+    always,
+    StartupPolicy::Always,
+    datetime(2018, 8, 13, 12, 0, 0),
+    true
+);
+startup_test!(
+    system_behind,
+    StartupPolicy::IfSystemBehind,
+    datetime(2018, 8, 13, 11, 59, 59),
+    true
+);
+startup_test!(
+    system_ahead,
+    StartupPolicy::IfSystemBehind,
+    datetime(2018, 8, 13, 12, 0, 1),
+    false
+);
+startup_test!(
+    drift_exceeded,
+    StartupPolicy::IfDriftExceeds(10),
+    datetime(2018, 8, 13, 12, 0, 11),
+    true
+);
+startup_test!(
+    drift_not_exceeded,
+    StartupPolicy::IfDriftExceeds(10),
+    datetime(2018, 8, 13, 11, 59, 50),
+    false
+);
+
+#[test]
+fn can_write_system_time_to_rtc() {
+    let mut rtc = new(&[
+        read_datetime(&datetime(2018, 8, 13, 12, 0, 0)),
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x03, 0, 0x12, 0x02, 0x13, 0x08, 0x18],
+        ),
+    ]);
+    let mut clock = FakeClock::new(datetime(2018, 8, 13, 12, 0, 3));
+    let report = ClockSync::default()
+        .system_to_rtc(&mut rtc, &mut clock)
+        .unwrap();
+    assert!(report.rtc_updated);
+    assert!(!report.system_updated);
+    assert_eq!(TimeDelta::seconds(-3), report.drift());
+    assert_eq!(None, clock.set_to);
+    destroy(rtc);
+}
+
+#[test]
+fn system_clock_errors_are_returned() {
+    let mut rtc = new(&[read_datetime(&datetime(2018, 8, 13, 12, 0, 0))]);
+    let mut clock = FakeClock::new(datetime(2018, 8, 13, 12, 0, 0));
+    clock.fail = true;
+    match ClockSync::default().rtc_to_system(&mut rtc, &mut clock) {
+        Err(SyncError::SystemClock(ClockError)) => (),
+        _ => panic!("SystemClock error not returned."),
+    }
+    destroy(rtc);
+}
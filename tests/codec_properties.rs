@@ -0,0 +1,107 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::bcd::{decimal_to_packed_bcd, hours_register_to_h24, packed_bcd_to_decimal};
+use ds1307::{
+    DateTimeAccess, Datelike, Ds1307, Hours, NaiveDate, NaiveDateTime, Rtcc, TimeDelta, Timelike,
+};
+use proptest::prelude::*;
+mod common;
+use crate::common::{Register, RegisterImage};
+
+/// Seconds between 2000-01-01 00:00:00 and 2100-01-01 00:00:00.
+const CENTURY_SECONDS: i64 = 36_525 * 24 * 60 * 60;
+
+fn datetime_in_range() -> impl Strategy<Value = NaiveDateTime> {
+    (0..CENTURY_SECONDS).prop_map(|seconds| {
+        NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            + TimeDelta::seconds(seconds)
+    })
+}
+
+fn to_h12(hour: u32) -> Hours {
+    let h12 = match hour % 12 {
+        0 => 12,
+        h => h as u8,
+    };
+    if hour < 12 {
+        Hours::AM(h12)
+    } else {
+        Hours::PM(h12)
+    }
+}
+
+proptest! {
+    #[test]
+    fn bcd_round_trips(value in 0u8..=99) {
+        prop_assert_eq!(value, packed_bcd_to_decimal(decimal_to_packed_bcd(value)));
+    }
+
+    #[test]
+    fn h12_and_h24_registers_agree(hour in 0u8..24) {
+        let h24 = decimal_to_packed_bcd(hour);
+        let h12 = match to_h12(u32::from(hour)) {
+            Hours::AM(h) => 0b0100_0000 | decimal_to_packed_bcd(h),
+            Hours::PM(h) => 0b0110_0000 | decimal_to_packed_bcd(h),
+            Hours::H24(_) => unreachable!(),
+        };
+        prop_assert_eq!(hour, hours_register_to_h24(h24));
+        prop_assert_eq!(hour, hours_register_to_h24(h12));
+    }
+
+    #[test]
+    fn datetime_round_trips_in_24h_mode(datetime in datetime_in_range()) {
+        let mut rtc = Ds1307::new(RegisterImage::new([0; 64]));
+        rtc.set_datetime(&datetime).unwrap();
+        prop_assert_eq!(datetime, rtc.datetime().unwrap());
+        prop_assert_eq!(Hours::H24(datetime.hour() as u8), rtc.hours().unwrap());
+        prop_assert_eq!(
+            datetime.weekday().number_from_sunday() as u8,
+            rtc.weekday().unwrap()
+        );
+    }
+
+    #[test]
+    fn datetime_round_trips_in_12h_mode(datetime in datetime_in_range()) {
+        let mut rtc = Ds1307::new(RegisterImage::new([0; 64]));
+        rtc.set_datetime(&datetime).unwrap();
+        rtc.set_hours(to_h12(datetime.hour())).unwrap();
+        prop_assert_eq!(to_h12(datetime.hour()), rtc.hours().unwrap());
+        prop_assert_eq!(datetime, rtc.datetime().unwrap());
+        prop_assert_eq!(datetime.date(), rtc.date().unwrap());
+        prop_assert_eq!(datetime.time(), rtc.time().unwrap());
+    }
+
+    #[test]
+    fn set_datetime_keeps_clock_halt(datetime in datetime_in_range(), halted: bool) {
+        let mut registers = [0; 64];
+        registers[usize::from(Register::SECONDS)] = if halted { 0x80 } else { 0 };
+        let mut rtc = Ds1307::new(RegisterImage::new(registers));
+        rtc.set_datetime(&datetime).unwrap();
+        prop_assert_eq!(!halted, rtc.running().unwrap());
+        prop_assert_eq!(datetime, rtc.datetime().unwrap());
+    }
+
+    #[test]
+    fn getters_do_not_panic_on_arbitrary_registers(registers in prop::collection::vec(any::<u8>(), 64)) {
+        let mut image = [0; 64];
+        image.copy_from_slice(&registers);
+        let mut rtc = Ds1307::new(RegisterImage::new(image));
+        let _ = rtc.datetime();
+        let _ = rtc.date();
+        let _ = rtc.time();
+        let _ = rtc.seconds();
+        let _ = rtc.minutes();
+        let _ = rtc.hours();
+        let _ = rtc.weekday();
+        let _ = rtc.day();
+        let _ = rtc.month();
+        let _ = rtc.year();
+        let _ = rtc.running();
+        let _ = rtc.square_wave_output_enabled();
+        let _ = rtc.square_wave_output_level();
+        let _ = rtc.square_wave_output_rate();
+    }
+}
@@ -1,4 +1,6 @@
 use ds1307::Ds1307;
+#[cfg(feature = "chrono")]
+use ds1307::{Datelike, NaiveDate, NaiveDateTime, Timelike};
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
 
 pub const ADDR: u8 = 0b110_1000;
@@ -18,14 +20,98 @@ impl Register {
     pub const RAM_END: u8 = 0x3F;
 }
 
+#[allow(unused)]
 pub fn new(transactions: &[I2cTrans]) -> Ds1307<I2cMock> {
     Ds1307::new(I2cMock::new(transactions))
 }
 
+#[allow(unused)]
 pub fn destroy(dev: Ds1307<I2cMock>) {
     dev.destroy().done();
 }
 
+/// CRC-8 as used by the driver to protect data in user RAM.
+#[allow(unused)]
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0xFF;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x31
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Offset in the user RAM used by the tests of types persisting data there.
+#[allow(unused)]
+pub const OFFSET: u8 = 4;
+
+/// Expect reading the raw date and time registers.
+#[allow(unused)]
+pub fn read_datetime_registers(registers: [u8; 7]) -> I2cTrans {
+    I2cTrans::write_read(ADDR, vec![Register::SECONDS], registers.to_vec())
+}
+
+/// Expect reading the user RAM at `offset`.
+#[allow(unused)]
+pub fn read_ram(offset: u8, data: Vec<u8>) -> I2cTrans {
+    I2cTrans::write_read(ADDR, vec![Register::RAM_BEGIN + offset], data)
+}
+
+/// Expect writing the user RAM at `offset`.
+#[allow(unused)]
+pub fn write_ram(offset: u8, data: Vec<u8>) -> I2cTrans {
+    let mut payload = vec![Register::RAM_BEGIN + offset];
+    payload.extend(data);
+    I2cTrans::write(ADDR, payload)
+}
+
+#[cfg(feature = "chrono")]
+#[allow(unused)]
+pub fn datetime(year: i32, month: u32, day: u32, h: u32, min: u32, s: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(h, min, s)
+        .unwrap()
+}
+
+/// Expect reading `datetime` in 24-hour format with Sunday as day 1.
+#[cfg(feature = "chrono")]
+#[allow(unused)]
+pub fn read_datetime(datetime: &NaiveDateTime) -> I2cTrans {
+    let bcd = |value: u32| (((value / 10) << 4) | (value % 10)) as u8;
+    read_datetime_registers([
+        bcd(datetime.second()),
+        bcd(datetime.minute()),
+        bcd(datetime.hour()),
+        datetime.weekday().number_from_sunday() as u8,
+        bcd(datetime.day()),
+        bcd(datetime.month()),
+        bcd(datetime.year() as u32 - 2000),
+    ])
+}
+
+/// Seconds since 2000-01-01 00:00:00 as stored by the driver in user RAM.
+#[cfg(feature = "chrono")]
+#[allow(unused)]
+pub fn rtc_epoch_seconds(datetime: &NaiveDateTime) -> u32 {
+    (*datetime - self::datetime(2000, 1, 1, 0, 0, 0)).num_seconds() as u32
+}
+
+/// A date and time as stored by the driver in user RAM, followed by its CRC.
+#[cfg(feature = "chrono")]
+#[allow(unused)]
+pub fn stored_datetime(datetime: &NaiveDateTime) -> Vec<u8> {
+    let mut data = rtc_epoch_seconds(datetime).to_le_bytes().to_vec();
+    data.push(crc8(&data));
+    data
+}
+
 #[macro_export]
 macro_rules! assert_invalid_input_data {
     ($result:expr) => {
@@ -85,3 +171,58 @@ macro_rules! set_test {
         }
     };
 }
+
+/// Simulated device backed by the 64-byte register image.
+///
+/// Like the real device, the register pointer is set by the first written byte
+/// and auto-increments, wrapping around after the last RAM register.
+#[allow(unused)]
+#[derive(Debug, Clone)]
+pub struct RegisterImage {
+    pub registers: [u8; 64],
+    pointer: usize,
+}
+
+#[allow(unused)]
+impl RegisterImage {
+    pub fn new(registers: [u8; 64]) -> Self {
+        RegisterImage {
+            registers,
+            pointer: 0,
+        }
+    }
+}
+
+impl embedded_hal::i2c::ErrorType for RegisterImage {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::i2c::I2c for RegisterImage {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        assert_eq!(ADDR, address);
+        for operation in operations {
+            match operation {
+                embedded_hal::i2c::Operation::Write(data) => {
+                    if let Some((register, payload)) = data.split_first() {
+                        self.pointer = usize::from(*register) % self.registers.len();
+                        for byte in payload {
+                            self.registers[self.pointer] = *byte;
+                            self.pointer = (self.pointer + 1) % self.registers.len();
+                        }
+                    }
+                }
+                embedded_hal::i2c::Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.registers[self.pointer];
+                        self.pointer = (self.pointer + 1) % self.registers.len();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
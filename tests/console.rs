@@ -0,0 +1,233 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{Console, ConsoleError, Ds1307, Error};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+mod common;
+use crate::common::{datetime, destroy, new, read_datetime, Register, ADDR};
+
+type ErrorKind = embedded_hal::i2c::ErrorKind;
+
+fn execute(rtc: &mut Ds1307<I2cMock>, line: &str) -> Result<String, ConsoleError<ErrorKind>> {
+    let mut out = String::new();
+    Console::new().execute(rtc, line, &mut out)?;
+    Ok(out)
+}
+
+#[test]
+fn empty_line_does_nothing() {
+    let mut rtc = new(&[]);
+    assert_eq!("", execute(&mut rtc, "   ").unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_print_help() {
+    let mut rtc = new(&[]);
+    let help = execute(&mut rtc, "help").unwrap();
+    assert!(help.contains("ram dump"));
+    destroy(rtc);
+}
+
+#[test]
+fn can_print_time() {
+    let mut rtc = new(&[read_datetime(&datetime(2018, 8, 13, 23, 59, 58))]);
+    assert_eq!("2018-08-13 23:59:58\n", execute(&mut rtc, "time").unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_print_time_with_format() {
+    let mut rtc = new(&[read_datetime(&datetime(2018, 8, 13, 23, 59, 58))]);
+    let mut out = String::new();
+    Console::with_format("%I:%M %p")
+        .execute(&mut rtc, "time", &mut out)
+        .unwrap();
+    assert_eq!("11:59 PM\n", out);
+    destroy(rtc);
+}
+
+#[test]
+fn can_set_time() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0x00]),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x58, 0x59, 0x23, 2, 0x13, 0x08, 0x18],
+        ),
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 58)),
+    ]);
+    let out = execute(&mut rtc, "set 2018-08-13 23:59:58").unwrap();
+    assert_eq!("2018-08-13 23:59:58\n", out);
+    destroy(rtc);
+}
+
+#[test]
+fn arguments_can_be_separated_by_any_whitespace() {
+    let data: Vec<u8> = (0..20).collect();
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0x00]),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x58, 0x59, 0x23, 2, 0x13, 0x08, 0x18],
+        ),
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 58)),
+        I2cTrans::write_read(ADDR, vec![Register::RAM_BEGIN + 4], data),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0001_0000]),
+    ]);
+    let out = execute(&mut rtc, "set\t2018-08-13  \t23:59:58").unwrap();
+    assert_eq!("2018-08-13 23:59:58\n", out);
+    let out = execute(&mut rtc, "ram  dump\t4 \t20").unwrap();
+    assert_eq!(2, out.lines().count());
+    execute(&mut rtc, " sqw\ton\t").unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_set_argument_is_error() {
+    let mut rtc = new(&[]);
+    match execute(&mut rtc, "set tomorrow") {
+        Err(ConsoleError::InvalidArgument) => (),
+        _ => panic!("InvalidArgument error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn can_halt_and_run() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0x12]),
+        I2cTrans::write(ADDR, vec![Register::SECONDS, 0x80 | 0x12]),
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0x80 | 0x12]),
+        I2cTrans::write(ADDR, vec![Register::SECONDS, 0x12]),
+    ]);
+    assert_eq!("", execute(&mut rtc, "halt").unwrap());
+    assert_eq!("", execute(&mut rtc, "run").unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_print_status() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0x80]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b1001_0001]),
+    ]);
+    assert_eq!(
+        "clock: halted\nsqw: on, 4096 Hz, level high\n",
+        execute(&mut rtc, "status").unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn can_configure_square_wave() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0001_0000]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0001_0000]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0001_0011]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0001_0011]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0000_0011]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0000_0011]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b1000_0011]),
+    ]);
+    execute(&mut rtc, "sqw on").unwrap();
+    execute(&mut rtc, "sqw 0x8000").unwrap();
+    execute(&mut rtc, "sqw off").unwrap();
+    execute(&mut rtc, "sqw high").unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_square_wave_rate_is_error() {
+    let mut rtc = new(&[]);
+    match execute(&mut rtc, "sqw 1000") {
+        Err(ConsoleError::InvalidArgument) => (),
+        _ => panic!("InvalidArgument error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn can_dump_ram() {
+    let data: Vec<u8> = (0..20).collect();
+    let mut rtc = new(&[I2cTrans::write_read(
+        ADDR,
+        vec![Register::RAM_BEGIN + 4],
+        data,
+    )]);
+    assert_eq!(
+        "04: 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f\n14: 10 11 12 13\n",
+        execute(&mut rtc, "ram dump 4 20").unwrap()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn can_dump_whole_ram() {
+    let mut rtc = new(&[I2cTrans::write_read(
+        ADDR,
+        vec![Register::RAM_BEGIN],
+        vec![0; 56],
+    )]);
+    let out = execute(&mut rtc, "ram dump").unwrap();
+    assert_eq!(4, out.lines().count());
+    assert!(out.ends_with("30: 00 00 00 00 00 00 00 00\n"));
+    destroy(rtc);
+}
+
+#[test]
+fn can_poke_ram() {
+    let mut rtc = new(&[I2cTrans::write(
+        ADDR,
+        vec![Register::RAM_BEGIN + 2, 0xAB, 12],
+    )]);
+    assert_eq!("", execute(&mut rtc, "ram poke 2 0xAB 12").unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_ram_arguments_are_errors() {
+    let mut rtc = new(&[]);
+    for line in [
+        "ram",
+        "ram peek",
+        "ram poke 2",
+        "ram poke 2 256",
+        "ram dump 0 57",
+        "ram dump 56",
+        "ram dump 60",
+        "ram dump x",
+        "ram dump 0 1 2",
+    ] {
+        match execute(&mut rtc, line) {
+            Err(ConsoleError::InvalidArgument) => (),
+            _ => panic!("InvalidArgument error not returned for {line}."),
+        }
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn out_of_range_ram_access_is_rtc_error() {
+    let mut rtc = new(&[]);
+    match execute(&mut rtc, "ram dump 50 10") {
+        Err(ConsoleError::Rtc(Error::InvalidInputData)) => (),
+        _ => panic!("InvalidInputData error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn unknown_command_is_error() {
+    let mut rtc = new(&[]);
+    match execute(&mut rtc, "reboot") {
+        Err(ConsoleError::UnknownCommand) => (),
+        _ => panic!("UnknownCommand error not returned."),
+    }
+    match execute(&mut rtc, "halt now") {
+        Err(ConsoleError::InvalidArgument) => (),
+        _ => panic!("InvalidArgument error not returned."),
+    }
+    destroy(rtc);
+}
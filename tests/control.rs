@@ -0,0 +1,79 @@
+use ds1307::{ControlConfig, SqwOutLevel, SqwOutRate};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{destroy, new, Register, ADDR};
+
+get_test!(
+    get_power_on,
+    control,
+    ControlConfig::default(),
+    trans_read!(SQWOUT, [0b0000_0011])
+);
+
+get_test!(
+    get_all_set,
+    control,
+    ControlConfig {
+        sqwe: true,
+        out_level: SqwOutLevel::High,
+        rate: SqwOutRate::Khz4_096
+    },
+    trans_read!(SQWOUT, [0b1001_0001])
+);
+
+get_test!(
+    get_level_high_1hz,
+    control,
+    ControlConfig {
+        sqwe: false,
+        out_level: SqwOutLevel::High,
+        rate: SqwOutRate::Hz1
+    },
+    trans_read!(SQWOUT, [0b1000_0000])
+);
+
+set_test!(
+    set_all,
+    set_control,
+    ControlConfig {
+        sqwe: true,
+        out_level: SqwOutLevel::High,
+        rate: SqwOutRate::Khz8_192
+    },
+    trans_write!(SQWOUT, [0b1001_0010])
+);
+
+set_test!(
+    set_power_on,
+    set_control,
+    ControlConfig::default(),
+    trans_write!(SQWOUT, [0b0000_0011])
+);
+
+#[test]
+fn configure_starts_from_power_on_state() {
+    let mut dev = new(&trans_write!(SQWOUT, [0b0000_0011]));
+    assert_eq!(ControlConfig::default(), dev.configure().apply().unwrap());
+    destroy(dev);
+}
+
+#[test]
+fn configure_applies_everything_at_once() {
+    let mut dev = new(&trans_write!(SQWOUT, [0b1001_0000]));
+    let config = dev
+        .configure()
+        .square_wave_output(true)
+        .output_level(SqwOutLevel::High)
+        .output_rate(SqwOutRate::Hz1)
+        .apply()
+        .unwrap();
+    assert_eq!(
+        ControlConfig {
+            sqwe: true,
+            out_level: SqwOutLevel::High,
+            rate: SqwOutRate::Hz1
+        },
+        config
+    );
+    destroy(dev);
+}
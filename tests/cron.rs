@@ -0,0 +1,260 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{CronError, CronSchedule, Error, NaiveDateTime, Scheduler};
+mod common;
+use crate::common::{datetime, destroy, new, read_datetime, read_ram, stored_datetime, write_ram};
+
+fn schedule(expression: &str) -> CronSchedule {
+    expression.parse().unwrap()
+}
+
+fn next(expression: &str, after: NaiveDateTime) -> Option<NaiveDateTime> {
+    schedule(expression).next_fire(after)
+}
+
+#[test]
+fn can_parse_expressions() {
+    for expression in [
+        "* * * * *",
+        "0 0 1 1 0",
+        "59 23 31 12 7",
+        "*/15 9-17 * * 1-5",
+        "0,30 8-18/2 1,15 */3 *",
+        "  5  4 * * *  ",
+        "10/20 * * * *",
+    ] {
+        assert!(CronSchedule::parse(expression).is_ok(), "{expression}");
+    }
+}
+
+#[test]
+fn invalid_expressions_are_rejected() {
+    assert_eq!(Err(CronError::FieldCount), CronSchedule::parse(""));
+    assert_eq!(Err(CronError::FieldCount), CronSchedule::parse("* * * *"));
+    assert_eq!(
+        Err(CronError::FieldCount),
+        CronSchedule::parse("* * * * * *")
+    );
+    assert_eq!(
+        Err(CronError::InvalidField(0)),
+        CronSchedule::parse("60 * * * *")
+    );
+    assert_eq!(
+        Err(CronError::InvalidField(1)),
+        CronSchedule::parse("* 5-2 * * *")
+    );
+    assert_eq!(
+        Err(CronError::InvalidField(2)),
+        CronSchedule::parse("* * 0 * *")
+    );
+    assert_eq!(
+        Err(CronError::InvalidField(3)),
+        CronSchedule::parse("* * * */0 *")
+    );
+    assert_eq!(
+        Err(CronError::InvalidField(4)),
+        CronSchedule::parse("* * * * MON")
+    );
+    assert_eq!(
+        Err(CronError::InvalidField(0)),
+        CronSchedule::parse("1,,2 * * * *")
+    );
+}
+
+#[test]
+fn can_match() {
+    let schedule = schedule("*/15 9-17 * * 1-5");
+    // Monday
+    assert!(schedule.matches(&datetime(2024, 2, 5, 9, 45, 0)));
+    assert!(!schedule.matches(&datetime(2024, 2, 5, 9, 50, 0)));
+    assert!(!schedule.matches(&datetime(2024, 2, 5, 18, 0, 0)));
+    // Sunday
+    assert!(!schedule.matches(&datetime(2024, 2, 4, 9, 45, 0)));
+}
+
+#[test]
+fn next_fire_is_strictly_after() {
+    let after = datetime(2024, 2, 5, 3, 0, 0);
+    assert_eq!(
+        Some(datetime(2024, 2, 5, 3, 1, 0)),
+        next("* * * * *", after)
+    );
+    assert_eq!(
+        Some(datetime(2024, 2, 6, 3, 0, 0)),
+        next("0 3 * * *", after)
+    );
+    let after = after + ds1307::TimeDelta::seconds(30);
+    assert_eq!(
+        Some(datetime(2024, 2, 5, 3, 1, 0)),
+        next("* * * * *", after)
+    );
+}
+
+#[test]
+fn can_find_next_daily_and_weekly_fire() {
+    let after = datetime(2024, 2, 2, 7, 0, 0);
+    assert_eq!(
+        Some(datetime(2024, 2, 3, 6, 30, 0)),
+        next("30 6 * * *", after)
+    );
+    assert_eq!(
+        Some(datetime(2024, 2, 5, 6, 30, 0)),
+        next("30 6 * * 1", after)
+    );
+    assert_eq!(
+        Some(datetime(2024, 2, 4, 0, 0, 0)),
+        next("0 0 * * 7", after)
+    );
+    assert_eq!(
+        Some(datetime(2024, 2, 4, 0, 0, 0)),
+        next("0 0 * * 0", after)
+    );
+}
+
+#[test]
+fn can_roll_over_month_and_year() {
+    let after = datetime(2023, 12, 31, 23, 59, 0);
+    assert_eq!(
+        Some(datetime(2024, 1, 1, 0, 0, 0)),
+        next("* * * * *", after)
+    );
+    assert_eq!(
+        Some(datetime(2024, 3, 1, 0, 0, 0)),
+        next("0 0 1 3 *", after)
+    );
+    assert_eq!(
+        Some(datetime(2024, 1, 31, 12, 0, 0)),
+        next("0 12 31 * *", after)
+    );
+    let after = datetime(2024, 1, 31, 12, 0, 0);
+    assert_eq!(
+        Some(datetime(2024, 3, 31, 12, 0, 0)),
+        next("0 12 31 * *", after)
+    );
+}
+
+#[test]
+fn can_find_leap_day() {
+    let after = datetime(2024, 3, 1, 0, 0, 0);
+    assert_eq!(
+        Some(datetime(2028, 2, 29, 0, 0, 0)),
+        next("0 0 29 2 *", after)
+    );
+}
+
+#[test]
+fn impossible_schedule_never_fires() {
+    assert_eq!(None, next("0 0 30 2 *", datetime(2024, 1, 1, 0, 0, 0)));
+}
+
+#[test]
+fn day_of_month_or_week_fires_if_both_restricted() {
+    let schedule = schedule("0 0 13 * 5");
+    // Friday 2024-02-02
+    assert!(schedule.matches(&datetime(2024, 2, 2, 0, 0, 0)));
+    // Tuesday 2024-02-13
+    assert!(schedule.matches(&datetime(2024, 2, 13, 0, 0, 0)));
+    assert!(!schedule.matches(&datetime(2024, 2, 14, 0, 0, 0)));
+    // Only the day of the week is restricted
+    let schedule = CronSchedule::parse("0 0 * * 5").unwrap();
+    assert!(!schedule.matches(&datetime(2024, 2, 13, 0, 0, 0)));
+}
+
+#[test]
+fn first_check_reports_nothing() {
+    let jobs = [schedule("* * * * *")];
+    let mut rtc = new(&[read_datetime(&datetime(2024, 2, 5, 3, 0, 0))]);
+    let mut scheduler = Scheduler::new(&jobs).unwrap();
+    assert!(scheduler.due_jobs(&mut rtc).unwrap().is_empty());
+    assert_eq!(Some(datetime(2024, 2, 5, 3, 0, 0)), scheduler.last_check());
+    destroy(rtc);
+}
+
+#[test]
+fn reports_jobs_fired_since_last_check() {
+    let jobs = [
+        schedule("0 3 * * *"),
+        schedule("30 3 * * *"),
+        schedule("15 3 * * *"),
+    ];
+    let mut rtc = new(&[
+        read_datetime(&datetime(2024, 2, 5, 2, 59, 0)),
+        read_datetime(&datetime(2024, 2, 5, 3, 20, 0)),
+        read_datetime(&datetime(2024, 2, 5, 3, 20, 0)),
+    ]);
+    let mut scheduler = Scheduler::new(&jobs).unwrap();
+    scheduler.due_jobs(&mut rtc).unwrap();
+    let due = scheduler.due_jobs(&mut rtc).unwrap();
+    assert_eq!(2, due.len());
+    assert!(due.contains(0));
+    assert!(!due.contains(1));
+    assert!(due.contains(2));
+    assert!(!due.contains(40));
+    let mut indices = due.iter();
+    assert_eq!(Some(0), indices.next());
+    assert_eq!(Some(2), indices.next());
+    assert_eq!(None, indices.next());
+    assert!(scheduler.due_jobs(&mut rtc).unwrap().is_empty());
+    destroy(rtc);
+}
+
+#[test]
+fn too_many_jobs_are_rejected() {
+    let jobs = [schedule("* * * * *"); 33];
+    assert!(Scheduler::new(&jobs).is_none());
+    assert!(Scheduler::new(&jobs[..32]).is_some());
+    let mut rtc = new(&[]);
+    match Scheduler::load(&mut rtc, 0, &jobs) {
+        Err(Error::InvalidInputData) => (),
+        _ => panic!("InvalidInputData error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn reports_jobs_missed_while_off() {
+    let jobs = [schedule("0 3 * * *"), schedule("0 12 * * *")];
+    let last_check = datetime(2024, 2, 4, 23, 0, 0);
+    let now = datetime(2024, 2, 5, 4, 0, 0);
+    let mut rtc = new(&[
+        read_ram(0, stored_datetime(&last_check)),
+        read_datetime(&datetime(2024, 2, 5, 4, 0, 0)),
+        write_ram(0, stored_datetime(&now)),
+    ]);
+    let mut scheduler = Scheduler::load(&mut rtc, 0, &jobs).unwrap();
+    assert_eq!(Some(last_check), scheduler.last_check());
+    let due = scheduler.due_jobs(&mut rtc).unwrap();
+    assert!(due.contains(0));
+    assert!(!due.contains(1));
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_stored_check_is_ignored() {
+    let jobs = [schedule("0 3 * * *")];
+    let mut data = stored_datetime(&datetime(2024, 2, 4, 23, 0, 0));
+    data[4] ^= 1;
+    let mut rtc = new(&[
+        read_ram(0, data),
+        read_datetime(&datetime(2024, 2, 5, 4, 0, 0)),
+        write_ram(0, stored_datetime(&datetime(2024, 2, 5, 4, 0, 0))),
+    ]);
+    let mut scheduler = Scheduler::load(&mut rtc, 0, &jobs).unwrap();
+    assert_eq!(None, scheduler.last_check());
+    assert!(scheduler.due_jobs(&mut rtc).unwrap().is_empty());
+    destroy(rtc);
+}
+
+#[test]
+fn nothing_is_due_after_clock_went_backwards() {
+    let jobs = [schedule("* * * * *")];
+    let mut rtc = new(&[
+        read_datetime(&datetime(2024, 2, 5, 4, 0, 0)),
+        read_datetime(&datetime(2024, 2, 5, 3, 0, 0)),
+    ]);
+    let mut scheduler = Scheduler::new(&jobs).unwrap();
+    scheduler.due_jobs(&mut rtc).unwrap();
+    assert!(scheduler.due_jobs(&mut rtc).unwrap().is_empty());
+    assert_eq!(Some(datetime(2024, 2, 5, 3, 0, 0)), scheduler.last_check());
+    destroy(rtc);
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "chrono")]
+
 use ds1307::{DateTimeAccess, Error, NaiveDate, NaiveDateTime, NaiveTime, Rtcc};
 use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
 mod common;
@@ -0,0 +1,110 @@
+#![cfg(feature = "fugit")]
+
+use ds1307::{
+    datetime_to_instant, duration_between, instant_to_datetime, ElapsedError, EpochDuration,
+    EpochInstant, Error, NaiveDate,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{
+    datetime, destroy, new, read_datetime, read_datetime_registers, Register, ADDR,
+};
+
+// 2018-08-13 23:59:58
+const TIMESTAMP: u64 = 1_534_204_798;
+
+#[test]
+fn can_convert_datetime_to_instant() {
+    assert_eq!(
+        Some(EpochInstant::from_ticks(TIMESTAMP)),
+        datetime_to_instant(&datetime(2018, 8, 13, 23, 59, 58))
+    );
+    let before_epoch = NaiveDate::from_ymd_opt(1969, 12, 31)
+        .unwrap()
+        .and_hms_opt(23, 59, 59)
+        .unwrap();
+    assert_eq!(None, datetime_to_instant(&before_epoch));
+}
+
+#[test]
+fn can_convert_instant_to_datetime() {
+    assert_eq!(
+        Some(datetime(2018, 8, 13, 23, 59, 58)),
+        instant_to_datetime(EpochInstant::from_ticks(TIMESTAMP))
+    );
+    assert_eq!(
+        None,
+        instant_to_datetime(EpochInstant::from_ticks(u64::MAX))
+    );
+}
+
+#[test]
+fn can_compute_duration_between() {
+    let later = datetime(2018, 8, 13, 23, 59, 58) + chrono::Duration::seconds(90);
+    assert_eq!(
+        Some(EpochDuration::from_ticks(90)),
+        duration_between(&datetime(2018, 8, 13, 23, 59, 58), &later)
+    );
+    assert_eq!(
+        None,
+        duration_between(&later, &datetime(2018, 8, 13, 23, 59, 58))
+    );
+}
+
+#[test]
+fn can_read_instant() {
+    let mut dev = new(&[read_datetime(&datetime(2018, 8, 13, 23, 59, 58))]);
+    assert_eq!(EpochInstant::from_ticks(TIMESTAMP), dev.instant().unwrap());
+    destroy(dev);
+}
+
+#[test]
+fn can_take_snapshot() {
+    let mut dev = new(&[read_datetime_registers([
+        0b1101_1000,
+        0x59,
+        0x23,
+        2,
+        0x13,
+        0x08,
+        0x18,
+    ])]);
+    let snapshot = dev.snapshot().unwrap();
+    assert_eq!(datetime(2018, 8, 13, 23, 59, 58), snapshot.datetime());
+    assert!(!snapshot.running());
+    assert_eq!(EpochInstant::from_ticks(TIMESTAMP), snapshot.instant());
+    destroy(dev);
+}
+
+#[test]
+fn invalid_snapshot_returns_error() {
+    let mut dev = new(&trans_read!(SECONDS, [0, 0, 0, 1, 0x31, 0x02, 0x18]));
+    assert_invalid_input_data!(dev.snapshot());
+    destroy(dev);
+}
+
+#[test]
+fn can_compute_duration_between_snapshots() {
+    let mut dev = new(&[
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 58)),
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 59)),
+    ]);
+    let start = dev.snapshot().unwrap();
+    let end = dev.snapshot().unwrap();
+    assert_eq!(Ok(EpochDuration::from_ticks(1)), end.duration_since(&start));
+    assert_eq!(Err(ElapsedError::Backwards), start.duration_since(&end));
+    destroy(dev);
+}
+
+#[test]
+fn duration_between_halted_snapshots_returns_error() {
+    let mut dev = new(&[
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 58)),
+        read_datetime_registers([0b1101_1000, 0x59, 0x23, 2, 0x13, 0x08, 0x18]),
+    ]);
+    let start = dev.snapshot().unwrap();
+    let end = dev.snapshot().unwrap();
+    assert_eq!(Err(ElapsedError::Halted), end.duration_since(&start));
+    assert_eq!(Err(ElapsedError::Halted), start.duration_since(&end));
+    destroy(dev);
+}
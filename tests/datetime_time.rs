@@ -0,0 +1,92 @@
+#![cfg(feature = "time")]
+
+use ds1307::Error;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use time::{Date, Month, PrimitiveDateTime, Time};
+mod common;
+use crate::common::{destroy, new, Register, ADDR};
+
+fn get_valid_datetime() -> PrimitiveDateTime {
+    PrimitiveDateTime::new(
+        Date::from_calendar_date(2018, Month::August, 13).unwrap(),
+        Time::from_hms(23, 59, 58).unwrap(),
+    )
+}
+
+#[test]
+fn get_datetime() {
+    let mut dev = new(&trans_read!(
+        SECONDS,
+        [
+            0b1101_1000,
+            0b0101_1001,
+            0b0010_0011,
+            0b0000_0010,
+            0b0001_0011,
+            0b0000_1000,
+            0b0001_1000
+        ]
+    ));
+    assert_eq!(get_valid_datetime(), dev.primitive_datetime().unwrap());
+    destroy(dev);
+}
+
+#[test]
+fn get_invalid_datetime_returns_error() {
+    let mut dev = new(&trans_read!(SECONDS, [0, 0, 0, 1, 0x31, 0x02, 0x18]));
+    assert_invalid_input_data!(dev.primitive_datetime());
+    destroy(dev);
+}
+
+#[test]
+fn can_set_datetime() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0b1101_1000]),
+        I2cTrans::write(
+            ADDR,
+            vec![
+                Register::SECONDS,
+                0b1101_1000,
+                0b0101_1001,
+                0b0010_0011,
+                0b0000_0010,
+                0b0001_0011,
+                0b0000_1000,
+                0b0001_1000,
+            ],
+        ),
+    ]);
+    rtc.set_primitive_datetime(&get_valid_datetime()).unwrap();
+    destroy(rtc);
+}
+
+set_invalid_test!(
+    year_too_small,
+    set_primitive_datetime,
+    &PrimitiveDateTime::new(
+        Date::from_calendar_date(1999, Month::January, 1).unwrap(),
+        Time::MIDNIGHT
+    )
+);
+set_invalid_test!(
+    year_too_big,
+    set_primitive_datetime,
+    &PrimitiveDateTime::new(
+        Date::from_calendar_date(2100, Month::January, 1).unwrap(),
+        Time::MIDNIGHT
+    )
+);
+
+#[test]
+fn set_datetime_honours_weekday_convention() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x58, 0x59, 0x23, 1, 0x13, 0x08, 0x18],
+        ),
+    ]);
+    rtc.set_weekday_convention(ds1307::WeekdayConvention::MondayOne);
+    rtc.set_primitive_datetime(&get_valid_datetime()).unwrap();
+    destroy(rtc);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{Error, Event, EventLog};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{crc8, datetime, destroy, new, read_datetime, read_ram, write_ram};
+
+const OFFSET: u8 = 20;
+const LENGTH: u8 = 28;
+const ENTRIES: u8 = OFFSET + 8;
+
+fn header(sequence: u8, head: u8, count: u8) -> Vec<u8> {
+    vec![sequence, head, count, crc8(&[sequence, head, count])]
+}
+
+fn entry(seconds: u32, code: u8) -> Vec<u8> {
+    let mut data = seconds.to_le_bytes().to_vec();
+    data.push(code);
+    data
+}
+
+fn read_headers(first: Vec<u8>, second: Vec<u8>) -> I2cTrans {
+    read_ram(OFFSET, [first, second].concat())
+}
+
+fn read_entries(entries: &[Vec<u8>]) -> I2cTrans {
+    read_ram(ENTRIES, entries.concat())
+}
+
+#[test]
+fn invalid_log_is_empty() {
+    let mut rtc = new(&[read_headers(vec![0; 4], vec![0xFF; 4])]);
+    let log = EventLog::open(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert!(log.is_empty());
+    assert_eq!(0, log.len());
+    assert_eq!(3, log.capacity());
+    destroy(rtc);
+}
+
+#[test]
+fn can_push_event() {
+    let mut rtc = new(&[
+        read_headers(vec![0; 4], vec![0; 4]),
+        read_datetime(&datetime(2000, 1, 1, 0, 0, 5)),
+        write_ram(ENTRIES, entry(5, 0xAB)),
+        write_ram(OFFSET, header(1, 0, 1)),
+    ]);
+    let mut log = EventLog::open(&mut rtc, OFFSET, LENGTH).unwrap();
+    log.push(&mut rtc, 0xAB).unwrap();
+    assert_eq!(1, log.len());
+    destroy(rtc);
+}
+
+#[test]
+fn uses_newest_valid_header_and_alternates() {
+    let mut rtc = new(&[
+        read_headers(header(255, 0, 1), header(0, 0, 2)),
+        write_ram(ENTRIES + 10, entry(7, 3)),
+        write_ram(OFFSET, header(1, 0, 3)),
+        write_ram(ENTRIES + 15, entry(8, 4)),
+        write_ram(OFFSET + 4, header(2, 1, 3)),
+    ]);
+    let mut log = EventLog::open(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert_eq!(2, log.len());
+    log.push_at(&mut rtc, 3, &datetime(2000, 1, 1, 0, 0, 7))
+        .unwrap();
+    log.push_at(&mut rtc, 4, &datetime(2000, 1, 1, 0, 0, 8))
+        .unwrap();
+    assert_eq!(3, log.len());
+    destroy(rtc);
+}
+
+#[test]
+fn torn_header_is_ignored() {
+    let mut torn = header(6, 1, 3);
+    torn[2] = 0;
+    let mut rtc = new(&[read_headers(header(5, 0, 2), torn)]);
+    let log = EventLog::open(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert_eq!(2, log.len());
+    destroy(rtc);
+}
+
+#[test]
+fn can_iterate_events_in_order() {
+    let mut rtc = new(&[
+        read_headers(header(5, 2, 3), header(4, 1, 3)),
+        read_entries(&[entry(3, 13), entry(0, 0), entry(1, 11), entry(2, 12)]),
+    ]);
+    let log = EventLog::open(&mut rtc, OFFSET, LENGTH).unwrap();
+    let events: Vec<Event> = log.iter(&mut rtc).unwrap().collect();
+    assert_eq!(
+        vec![
+            Event {
+                code: 11,
+                timestamp: datetime(2000, 1, 1, 0, 0, 1)
+            },
+            Event {
+                code: 12,
+                timestamp: datetime(2000, 1, 1, 0, 0, 2)
+            },
+            Event {
+                code: 13,
+                timestamp: datetime(2000, 1, 1, 0, 0, 3)
+            },
+        ],
+        events
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn can_clear() {
+    let mut rtc = new(&[
+        read_headers(header(5, 2, 3), header(4, 1, 3)),
+        write_ram(OFFSET + 4, header(6, 2, 0)),
+        read_entries(&[entry(0, 0), entry(0, 0), entry(0, 0), entry(0, 0)]),
+    ]);
+    let mut log = EventLog::open(&mut rtc, OFFSET, LENGTH).unwrap();
+    log.clear(&mut rtc).unwrap();
+    assert!(log.is_empty());
+    assert_eq!(0, log.iter(&mut rtc).unwrap().count());
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_region_returns_error() {
+    let mut rtc = new(&[]);
+    assert_invalid_input_data!(EventLog::open(&mut rtc, 0, 17));
+    assert_invalid_input_data!(EventLog::open(&mut rtc, 40, 18));
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_timestamp_returns_error() {
+    let mut rtc = new(&[read_headers(vec![0; 4], vec![0; 4])]);
+    let mut log = EventLog::open(&mut rtc, OFFSET, LENGTH).unwrap();
+    let before_2000 = datetime(1999, 12, 31, 23, 59, 59);
+    assert_invalid_input_data!(log.push_at(&mut rtc, 1, &before_2000));
+    destroy(rtc);
+}
@@ -0,0 +1,179 @@
+use ds1307::{Error, KvStore};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{crc8, destroy, new, Register, ADDR};
+
+const OFFSET: u8 = 10;
+const LENGTH: u8 = 12;
+
+fn image(records: &[u8]) -> Vec<u8> {
+    let mut data = vec![records.len() as u8];
+    data.extend_from_slice(records);
+    data.insert(0, crc8(&data));
+    data
+}
+
+fn read_region(records: &[u8]) -> I2cTrans {
+    let mut data = image(records);
+    data.resize(LENGTH as usize, 0);
+    I2cTrans::write_read(ADDR, vec![Register::RAM_BEGIN + OFFSET], data)
+}
+
+fn write_region(records: &[u8]) -> I2cTrans {
+    let mut payload = vec![Register::RAM_BEGIN + OFFSET];
+    payload.extend_from_slice(&image(records));
+    I2cTrans::write(ADDR, payload)
+}
+
+#[test]
+fn can_format() {
+    let mut rtc = new(&[write_region(&[])]);
+    let store = KvStore::format(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert_eq!(None, store.get(1));
+    assert_eq!(10, store.free());
+    destroy(rtc);
+}
+
+#[test]
+fn can_load_and_get() {
+    let mut rtc = new(&[read_region(&[1, 1, 0xAB, 2, 2, 0xCD, 0xEF])]);
+    let store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert_eq!(Some(&[0xAB][..]), store.get(1));
+    assert_eq!(Some(&[0xCD, 0xEF][..]), store.get(2));
+    assert_eq!(None, store.get(3));
+    assert_eq!(3, store.free());
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_crc_returns_corrupted_data() {
+    let mut data = image(&[1, 1, 0xAB]);
+    data[4] ^= 1;
+    data.resize(LENGTH as usize, 0);
+    let mut rtc = new(&[I2cTrans::write_read(
+        ADDR,
+        vec![Register::RAM_BEGIN + OFFSET],
+        data,
+    )]);
+    match KvStore::load(&mut rtc, OFFSET, LENGTH) {
+        Err(Error::CorruptedData) => (),
+        _ => panic!("CorruptedData error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_record_length_returns_corrupted_data() {
+    let mut rtc = new(&[read_region(&[1, 5, 0xAB])]);
+    match KvStore::load(&mut rtc, OFFSET, LENGTH) {
+        Err(Error::CorruptedData) => (),
+        _ => panic!("CorruptedData error not returned."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_region_returns_error() {
+    let mut rtc = new(&[]);
+    assert_invalid_input_data!(KvStore::load(&mut rtc, 0, 4));
+    assert_invalid_input_data!(KvStore::format(&mut rtc, 50, 7));
+    destroy(rtc);
+}
+
+#[test]
+fn can_set_new_value() {
+    let mut rtc = new(&[
+        read_region(&[1, 1, 0xAB]),
+        write_region(&[1, 1, 0xAB, 2, 1, 0xCD]),
+    ]);
+    let mut store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    store.set(&mut rtc, 2, &[0xCD]).unwrap();
+    assert_eq!(Some(&[0xCD][..]), store.get(2));
+    destroy(rtc);
+}
+
+#[test]
+fn same_length_value_is_replaced_in_place() {
+    let mut rtc = new(&[
+        read_region(&[1, 1, 0xAB, 2, 1, 0xCD]),
+        write_region(&[1, 1, 0x12, 2, 1, 0xCD]),
+    ]);
+    let mut store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    store.set(&mut rtc, 1, &[0x12]).unwrap();
+    assert_eq!(Some(&[0x12][..]), store.get(1));
+    destroy(rtc);
+}
+
+#[test]
+fn changed_length_value_is_appended() {
+    let mut rtc = new(&[
+        read_region(&[1, 1, 0xAB, 2, 1, 0xCD]),
+        write_region(&[0xFF, 1, 0xAB, 2, 1, 0xCD, 1, 2, 0x12, 0x34]),
+    ]);
+    let mut store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    store.set(&mut rtc, 1, &[0x12, 0x34]).unwrap();
+    assert_eq!(Some(&[0x12, 0x34][..]), store.get(1));
+    assert_eq!(0, store.free());
+    destroy(rtc);
+}
+
+#[test]
+fn can_remove() {
+    let mut rtc = new(&[
+        read_region(&[1, 1, 0xAB, 2, 1, 0xCD]),
+        write_region(&[0xFF, 1, 0xAB, 2, 1, 0xCD]),
+    ]);
+    let mut store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert!(store.remove(&mut rtc, 1).unwrap());
+    assert!(!store.remove(&mut rtc, 1).unwrap());
+    assert_eq!(None, store.get(1));
+    assert_eq!(Some(&[0xCD][..]), store.get(2));
+    destroy(rtc);
+}
+
+#[test]
+fn can_compact() {
+    let mut rtc = new(&[
+        read_region(&[0xFF, 1, 0xAB, 2, 1, 0xCD]),
+        write_region(&[2, 1, 0xCD]),
+    ]);
+    let mut store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert_eq!(4, store.free());
+    store.compact(&mut rtc).unwrap();
+    assert_eq!(7, store.free());
+    assert_eq!(Some(&[0xCD][..]), store.get(2));
+    destroy(rtc);
+}
+
+#[test]
+fn compacts_when_needed() {
+    let mut rtc = new(&[
+        read_region(&[0xFF, 3, 1, 2, 3, 2, 1, 0xCD]),
+        write_region(&[2, 1, 0xCD, 3, 4, 1, 2, 3, 4]),
+    ]);
+    let mut store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    store.set(&mut rtc, 3, &[1, 2, 3, 4]).unwrap();
+    assert_eq!(Some(&[1, 2, 3, 4][..]), store.get(3));
+    destroy(rtc);
+}
+
+#[test]
+fn overflow_returns_out_of_space() {
+    let mut rtc = new(&[read_region(&[1, 1, 0xAB, 2, 1, 0xCD])]);
+    let mut store = KvStore::load(&mut rtc, OFFSET, LENGTH).unwrap();
+    match store.set(&mut rtc, 3, &[1, 2, 3]) {
+        Err(Error::OutOfSpace) => (),
+        _ => panic!("OutOfSpace error not returned."),
+    }
+    assert_eq!(None, store.get(3));
+    assert_eq!(4, store.free());
+    destroy(rtc);
+}
+
+#[test]
+fn reserved_key_returns_error() {
+    let mut rtc = new(&[write_region(&[])]);
+    let mut store = KvStore::format(&mut rtc, OFFSET, LENGTH).unwrap();
+    assert_invalid_input_data!(store.set(&mut rtc, 0xFF, &[1]));
+    destroy(rtc);
+}
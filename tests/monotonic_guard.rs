@@ -0,0 +1,151 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{GuardError, JumpPolicy, MonotonicGuard, NaiveDateTime, TimeDelta, TimeJump};
+mod common;
+use crate::common::{datetime, destroy, new, read_datetime, read_ram, stored_datetime, write_ram};
+
+fn at(second: u32) -> NaiveDateTime {
+    datetime(2018, 8, 13, 23, 0, second)
+}
+
+fn max_forward() -> TimeDelta {
+    TimeDelta::hours(1)
+}
+
+#[test]
+fn first_reading_is_accepted() {
+    let mut rtc = new(&[read_datetime(&at(10))]);
+    let mut guard = MonotonicGuard::new(JumpPolicy::Clamp, max_forward());
+    assert_eq!(None, guard.last());
+    assert_eq!(at(10), guard.now(&mut rtc).unwrap());
+    assert_eq!(Some(at(10)), guard.last());
+    assert_eq!(None, guard.last_jump());
+    destroy(rtc);
+}
+
+#[test]
+fn clamps_backward_jump() {
+    let mut rtc = new(&[
+        read_datetime(&at(10)),
+        read_datetime(&at(5)),
+        read_datetime(&at(12)),
+    ]);
+    let mut guard = MonotonicGuard::new(JumpPolicy::Clamp, max_forward());
+    assert_eq!(at(10), guard.now(&mut rtc).unwrap());
+    assert_eq!(at(10), guard.now(&mut rtc).unwrap());
+    assert_eq!(
+        Some(TimeJump::Backward(TimeDelta::seconds(5))),
+        guard.last_jump()
+    );
+    assert_eq!(at(12), guard.now(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn clamp_accepts_large_forward_jump() {
+    let mut rtc = new(&[
+        read_datetime(&at(10)),
+        read_datetime(&datetime(2018, 8, 14, 2, 0, 0)),
+    ]);
+    let mut guard = MonotonicGuard::new(JumpPolicy::Clamp, max_forward());
+    guard.now(&mut rtc).unwrap();
+    let later = guard.now(&mut rtc).unwrap();
+    assert_eq!(at(10) + TimeDelta::seconds(3 * 3600 - 10), later);
+    assert_eq!(
+        Some(TimeJump::ForwardLarge(TimeDelta::seconds(3 * 3600 - 10))),
+        guard.last_jump()
+    );
+    destroy(rtc);
+}
+
+#[test]
+fn reports_backward_jump_once() {
+    let mut rtc = new(&[
+        read_datetime(&at(10)),
+        read_datetime(&at(5)),
+        read_datetime(&at(6)),
+    ]);
+    let mut guard = MonotonicGuard::new(JumpPolicy::Report, max_forward());
+    guard.now(&mut rtc).unwrap();
+    match guard.now(&mut rtc) {
+        Err(GuardError::Jump(TimeJump::Backward(delta))) => {
+            assert_eq!(TimeDelta::seconds(5), delta)
+        }
+        _ => panic!("Backward jump not reported."),
+    }
+    assert_eq!(at(6), guard.now(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn reports_large_forward_jump() {
+    let mut rtc = new(&[
+        read_datetime(&at(10)),
+        read_datetime(&datetime(2018, 8, 14, 2, 0, 0)),
+    ]);
+    let mut guard = MonotonicGuard::new(JumpPolicy::Report, max_forward());
+    guard.now(&mut rtc).unwrap();
+    match guard.now(&mut rtc) {
+        Err(GuardError::Jump(TimeJump::ForwardLarge(_))) => (),
+        _ => panic!("Forward jump not reported."),
+    }
+    destroy(rtc);
+}
+
+#[test]
+fn can_load_persisted_time() {
+    let mut rtc = new(&[read_ram(3, stored_datetime(&at(10))), read_datetime(&at(5))]);
+    let mut guard = MonotonicGuard::load(&mut rtc, 3, JumpPolicy::Clamp, max_forward()).unwrap();
+    assert_eq!(Some(at(10)), guard.last());
+    assert_eq!(at(10), guard.now(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn invalid_persisted_time_is_ignored() {
+    let mut rtc = new(&[
+        read_ram(0, vec![0; 5]),
+        read_datetime(&at(5)),
+        write_ram(0, stored_datetime(&at(5))),
+    ]);
+    let mut guard = MonotonicGuard::load(&mut rtc, 0, JumpPolicy::Clamp, max_forward()).unwrap();
+    assert_eq!(None, guard.last());
+    assert_eq!(at(5), guard.now(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn persists_only_changes() {
+    let mut rtc = new(&[
+        read_ram(0, stored_datetime(&at(10))),
+        read_datetime(&at(10)),
+        read_datetime(&at(11)),
+        write_ram(0, stored_datetime(&at(11))),
+    ]);
+    let mut guard = MonotonicGuard::load(&mut rtc, 0, JumpPolicy::Clamp, max_forward()).unwrap();
+    guard.now(&mut rtc).unwrap();
+    guard.now(&mut rtc).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn can_reset() {
+    let mut rtc = new(&[
+        read_ram(0, stored_datetime(&at(10))),
+        write_ram(0, vec![0; 5]),
+    ]);
+    let mut guard = MonotonicGuard::load(&mut rtc, 0, JumpPolicy::Clamp, max_forward()).unwrap();
+    guard.reset(&mut rtc).unwrap();
+    assert_eq!(None, guard.last());
+    destroy(rtc);
+}
+
+#[test]
+fn too_large_offset_is_invalid() {
+    let mut rtc = new(&[]);
+    match MonotonicGuard::load(&mut rtc, 52, JumpPolicy::Clamp, max_forward()) {
+        Err(ds1307::Error::InvalidInputData) => (),
+        _ => panic!("InvalidInputData error not returned."),
+    }
+    destroy(rtc);
+}
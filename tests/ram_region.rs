@@ -0,0 +1,59 @@
+use ds1307::{ram_layout, ram_layout_is_valid, RamRegion};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{destroy, new, Register, ADDR};
+
+ram_layout! {
+    Settings: 0, 4;
+    Counter: 4, 2;
+    Last: 55, 1;
+}
+
+#[test]
+fn can_read_region() {
+    let mut rtc = new(&[I2cTrans::write_read(
+        ADDR,
+        vec![Register::RAM_BEGIN + 4],
+        vec![0xAB, 0xCD],
+    )]);
+    assert_eq!([0xAB, 0xCD], Counter::new().read(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_write_region() {
+    let mut rtc = new(&[I2cTrans::write(ADDR, vec![Register::RAM_BEGIN, 1, 2, 3, 4])]);
+    Settings::new().write(&mut rtc, &[1, 2, 3, 4]).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn can_access_last_byte() {
+    let mut rtc = new(&[
+        I2cTrans::write(ADDR, vec![Register::RAM_END, 0xAB]),
+        I2cTrans::write_read(ADDR, vec![Register::RAM_END], vec![0xAB]),
+    ]);
+    let region = Last::default();
+    region.write(&mut rtc, &[0xAB]).unwrap();
+    assert_eq!([0xAB], region.read(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn region_constants() {
+    assert_eq!(4, Counter::OFFSET);
+    assert_eq!(2, Counter::LEN);
+    assert_eq!(0, RamRegion::<0, 56>::OFFSET);
+}
+
+#[test]
+fn can_validate_layout() {
+    assert!(ram_layout_is_valid(&[(0, 4), (4, 2), (55, 1)]));
+    assert!(ram_layout_is_valid(&[(0, 56)]));
+    assert!(!ram_layout_is_valid(&[(0, 57)]));
+    assert!(!ram_layout_is_valid(&[(55, 2)]));
+    assert!(!ram_layout_is_valid(&[(0, 0)]));
+    assert!(!ram_layout_is_valid(&[(0, 4), (3, 2)]));
+    assert!(!ram_layout_is_valid(&[(4, 2), (0, 5)]));
+    assert!(!ram_layout_is_valid(&[(0, 8), (2, 2)]));
+}
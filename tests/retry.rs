@@ -0,0 +1,118 @@
+use ds1307::{Ds1307, Error, RetryError, RetryI2c, RetryPolicy};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+mod common;
+use crate::common::{Register, ADDR};
+
+#[derive(Debug, Default)]
+struct RecordingDelay {
+    delays_ns: Vec<u32>,
+}
+
+impl DelayNs for RecordingDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delays_ns.push(ns);
+    }
+}
+
+const POLICY: RetryPolicy = RetryPolicy {
+    attempts: 3,
+    backoff_us: 100,
+};
+
+fn new(
+    transactions: &[I2cTrans],
+    policy: RetryPolicy,
+) -> Ds1307<RetryI2c<I2cMock, RecordingDelay>> {
+    let i2c = RetryI2c::new(
+        I2cMock::new(transactions),
+        RecordingDelay::default(),
+        policy,
+    );
+    Ds1307::new(i2c)
+}
+
+fn destroy(rtc: Ds1307<RetryI2c<I2cMock, RecordingDelay>>) -> Vec<u32> {
+    let (mut i2c, delay) = rtc.destroy().destroy();
+    i2c.done();
+    delay.delays_ns
+}
+
+fn nack(transaction: I2cTrans) -> I2cTrans {
+    transaction.with_error(ErrorKind::NoAcknowledge(
+        embedded_hal::i2c::NoAcknowledgeSource::Address,
+    ))
+}
+
+#[test]
+fn successful_transfer_is_not_retried() {
+    let mut rtc = new(&trans_read!(SECONDS, [0]), POLICY);
+    assert!(rtc.running().unwrap());
+    assert!(destroy(rtc).is_empty());
+}
+
+#[test]
+fn failed_read_is_retried_with_backoff() {
+    let read = I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]);
+    let mut rtc = new(&[nack(read.clone()), nack(read.clone()), read], POLICY);
+    assert!(rtc.running().unwrap());
+    assert_eq!(vec![100_000, 200_000], destroy(rtc));
+}
+
+#[test]
+fn gives_up_after_all_attempts() {
+    let read = I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]);
+    let mut rtc = new(
+        &[nack(read.clone()), nack(read.clone()), nack(read)],
+        POLICY,
+    );
+    match rtc.running() {
+        Err(Error::I2C(RetryError::I2C(_))) => (),
+        _ => panic!("I2C error not returned."),
+    }
+    assert_eq!(vec![100_000, 200_000], destroy(rtc));
+}
+
+#[test]
+fn failed_write_is_retried() {
+    let write = I2cTrans::write(ADDR, vec![Register::RAM_BEGIN, 0xAB]);
+    let mut rtc = new(&[nack(write.clone()), write], POLICY);
+    rtc.write_ram(0, &[0xAB]).unwrap();
+    assert_eq!(vec![100_000], destroy(rtc));
+}
+
+#[test]
+fn recovery_runs_after_each_failure() {
+    let read = I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]);
+    let mut recoveries = 0;
+    let i2c = RetryI2c::new(
+        I2cMock::new(&[nack(read.clone()), nack(read.clone()), read]),
+        RecordingDelay::default(),
+        POLICY,
+    )
+    .with_recovery(|_: &mut I2cMock| recoveries += 1);
+    let mut rtc = Ds1307::new(i2c);
+    assert!(rtc.running().unwrap());
+    let i2c = rtc.destroy();
+    assert_eq!(2, i2c.retries());
+    i2c.destroy().0.done();
+    assert_eq!(2, recoveries);
+}
+
+#[test]
+fn verify_failure_is_reported_by_driver() {
+    let mut rtc = new(
+        &[
+            I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0000_0011]),
+            I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0]),
+        ],
+        POLICY,
+    );
+    rtc.set_write_verification(true);
+    match rtc.set_control(Default::default()) {
+        Err(Error::VerifyFailed) => (),
+        _ => panic!("VerifyFailed error not returned."),
+    }
+    assert!(destroy(rtc).is_empty());
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "chrono")]
+
 use ds1307::{Error, Rtcc};
 use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
 mod common;
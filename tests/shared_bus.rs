@@ -0,0 +1,60 @@
+use core::cell::RefCell;
+use ds1307::{Ds1307, Ds1307Ref};
+use embedded_hal_bus::i2c::{CriticalSectionDevice, MutexDevice, RefCellDevice};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use std::sync::Mutex;
+mod common;
+use crate::common::{Register, ADDR};
+
+#[test]
+fn can_use_refcell_device() {
+    let mut mock = I2cMock::new(&trans_read!(SECONDS, [0]));
+    let bus = RefCell::new(mock.clone());
+    let mut rtc = Ds1307::new(RefCellDevice::new(&bus));
+    assert!(rtc.running().unwrap());
+    mock.done();
+}
+
+#[test]
+fn can_use_critical_section_device() {
+    let mut mock = I2cMock::new(&trans_read!(SECONDS, [0]));
+    let bus = critical_section::Mutex::new(RefCell::new(mock.clone()));
+    let mut rtc = Ds1307::new(CriticalSectionDevice::new(&bus));
+    assert!(rtc.running().unwrap());
+    mock.done();
+}
+
+#[test]
+fn can_use_mutex_device() {
+    let mut mock = I2cMock::new(&trans_read!(SECONDS, [0]));
+    let bus = Mutex::new(mock.clone());
+    let mut rtc = Ds1307::new(MutexDevice::new(&bus));
+    assert!(rtc.running().unwrap());
+    mock.done();
+}
+
+#[test]
+fn can_share_bus_between_instances() {
+    let mut mock = I2cMock::new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0001_0000]),
+    ]);
+    let bus = RefCell::new(mock.clone());
+    let mut rtc1 = Ds1307::new(RefCellDevice::new(&bus));
+    let mut rtc2 = Ds1307::new(RefCellDevice::new(&bus));
+    assert!(rtc1.running().unwrap());
+    assert!(rtc2.square_wave_output_enabled().unwrap());
+    mock.done();
+}
+
+#[test]
+fn can_borrow_bus() {
+    let mut bus = I2cMock::new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0b1000_0000]),
+    ]);
+    assert!(Ds1307::new(&mut bus).running().unwrap());
+    let mut rtc: Ds1307Ref<_> = Ds1307::new(&mut bus);
+    assert!(!rtc.running().unwrap());
+    bus.done();
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "solar")]
+
+use ds1307::{Location, NaiveDate, NaiveDateTime, TimeDelta};
+mod common;
+use crate::common::{datetime, destroy, new, read_datetime};
+
+fn london() -> Location {
+    // British Summer Time
+    Location::new(51.5074, -0.1278, TimeDelta::hours(1)).unwrap()
+}
+
+fn assert_near(expected: NaiveDateTime, actual: Option<NaiveDateTime>) {
+    let actual = actual.unwrap();
+    let difference = (actual - expected).num_seconds().abs();
+    assert!(difference <= 180, "expected {expected}, got {actual}");
+}
+
+#[test]
+fn invalid_location_is_rejected() {
+    assert!(Location::new(90.1, 0.0, TimeDelta::zero()).is_none());
+    assert!(Location::new(-90.1, 0.0, TimeDelta::zero()).is_none());
+    assert!(Location::new(0.0, 180.1, TimeDelta::zero()).is_none());
+    assert!(Location::new(f64::NAN, 0.0, TimeDelta::zero()).is_none());
+    assert!(Location::new(0.0, 0.0, TimeDelta::hours(19)).is_none());
+    assert!(Location::new(0.0, 0.0, TimeDelta::MIN).is_none());
+    assert!(Location::new(0.0, 0.0, TimeDelta::hours(-18)).is_some());
+    let location = Location::new(-33.8688, 151.2093, TimeDelta::hours(11)).unwrap();
+    assert_eq!(-33.8688, location.latitude());
+    assert_eq!(151.2093, location.longitude());
+    assert_eq!(TimeDelta::hours(11), location.utc_offset());
+}
+
+#[test]
+fn can_calculate_summer_solstice_in_london() {
+    let times = london().sun_times(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap());
+    assert_near(datetime(2024, 6, 21, 4, 43, 0), times.sunrise);
+    assert_near(datetime(2024, 6, 21, 21, 21, 0), times.sunset);
+    // Civil twilight lasts about 50 minutes around the solstice.
+    let twilight = times.sunrise.unwrap() - times.dawn.unwrap();
+    assert!((45..=55).contains(&twilight.num_minutes()), "{twilight}");
+    let twilight = times.dusk.unwrap() - times.sunset.unwrap();
+    assert!((45..=55).contains(&twilight.num_minutes()), "{twilight}");
+}
+
+#[test]
+fn can_calculate_winter_in_madrid() {
+    let madrid = Location::new(40.4168, -3.7038, TimeDelta::hours(1)).unwrap();
+    let times = madrid.sun_times(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap());
+    assert_near(datetime(2024, 12, 21, 8, 33, 0), times.sunrise);
+    assert_near(datetime(2024, 12, 21, 17, 51, 0), times.sunset);
+}
+
+#[test]
+fn can_calculate_southern_hemisphere() {
+    let sydney = Location::new(-33.8688, 151.2093, TimeDelta::hours(11)).unwrap();
+    let times = sydney.sun_times(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+    assert_near(datetime(2024, 1, 1, 5, 47, 0), times.sunrise);
+    assert_near(datetime(2024, 1, 1, 20, 9, 0), times.sunset);
+}
+
+#[test]
+fn polar_day_and_night_have_no_sunrise() {
+    let tromso = Location::new(69.6492, 18.9553, TimeDelta::hours(1)).unwrap();
+    let winter = tromso.sun_times(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap());
+    assert_eq!(None, winter.sunrise);
+    assert_eq!(None, winter.sunset);
+    assert!(winter.dawn.is_some());
+    assert!(!tromso.is_daylight_at(&datetime(2024, 12, 21, 12, 0, 0)));
+    let summer = tromso.sun_times(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap());
+    assert_eq!(None, summer.sunrise);
+    assert_eq!(None, summer.dusk);
+    assert!(tromso.is_daylight_at(&datetime(2024, 6, 21, 0, 0, 0)));
+}
+
+#[test]
+fn can_tell_daylight() {
+    let london = london();
+    assert!(!london.is_daylight_at(&datetime(2024, 6, 21, 4, 30, 0)));
+    assert!(london.is_daylight_at(&datetime(2024, 6, 21, 5, 0, 0)));
+    assert!(london.is_daylight_at(&datetime(2024, 6, 21, 21, 0, 0)));
+    assert!(!london.is_daylight_at(&datetime(2024, 6, 21, 21, 40, 0)));
+    assert!(london.is_civil_daylight_at(&datetime(2024, 6, 21, 21, 40, 0)));
+    assert!(!london.is_civil_daylight_at(&datetime(2024, 6, 21, 22, 30, 0)));
+    let noon = london.sun_elevation(&datetime(2024, 6, 21, 13, 0, 0));
+    assert!((61.0..63.0).contains(&noon), "{noon}");
+}
+
+#[test]
+fn can_read_daylight_from_rtc() {
+    let mut rtc = new(&[
+        read_datetime(&datetime(2024, 6, 21, 12, 0, 0)),
+        read_datetime(&datetime(2024, 6, 21, 23, 0, 0)),
+    ]);
+    assert!(london().is_daylight(&mut rtc).unwrap());
+    assert!(!london().is_daylight(&mut rtc).unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_read_sun_times_from_rtc() {
+    let mut rtc = new(&[read_datetime(&datetime(2024, 6, 21, 12, 0, 0))]);
+    let times = london().sun_times_today(&mut rtc).unwrap();
+    assert_near(datetime(2024, 6, 21, 4, 43, 0), times.sunrise);
+    destroy(rtc);
+}
+
+#[test]
+fn extreme_dates_do_not_panic() {
+    let location = Location::new(0.0, 0.0, TimeDelta::hours(18)).unwrap();
+    assert!(location.sun_times(NaiveDate::MAX).sunset.is_none());
+    let location = Location::new(0.0, 0.0, TimeDelta::hours(-18)).unwrap();
+    location.is_daylight_at(&NaiveDateTime::MIN);
+    location.sun_times(NaiveDate::MIN);
+}
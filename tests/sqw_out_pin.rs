@@ -0,0 +1,72 @@
+use ds1307::{Ds1307, SqwOutPin};
+use embedded_hal::digital::{OutputPin, StatefulOutputPin};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+mod common;
+use crate::common::{destroy, new, Register, ADDR};
+
+fn new_pin(transactions: &[I2cTrans]) -> SqwOutPin<I2cMock> {
+    let mut all = vec![
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b1001_0011]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b1000_0011]),
+    ];
+    all.extend_from_slice(transactions);
+    SqwOutPin::new(new(&all)).unwrap()
+}
+
+fn destroy_pin(pin: SqwOutPin<I2cMock>) {
+    destroy(pin.destroy());
+}
+
+#[test]
+fn new_disables_square_wave_output() {
+    let pin = new_pin(&[]);
+    destroy_pin(pin);
+}
+
+#[test]
+fn new_keeps_disabled_output() {
+    let rtc = Ds1307::new(I2cMock::new(&trans_read!(SQWOUT, [0b1000_0000])));
+    destroy(SqwOutPin::new(rtc).unwrap().destroy());
+}
+
+#[test]
+fn can_set_low() {
+    let mut pin = new_pin(&[
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b1000_0011]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0000_0011]),
+    ]);
+    pin.set_low().unwrap();
+    destroy_pin(pin);
+}
+
+#[test]
+fn can_set_high() {
+    let mut pin = new_pin(&[
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0000_0011]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b1000_0011]),
+    ]);
+    pin.set_high().unwrap();
+    destroy_pin(pin);
+}
+
+#[test]
+fn can_read_state() {
+    let mut pin = new_pin(&[
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b1000_0011]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b1000_0011]),
+    ]);
+    assert!(pin.is_set_high().unwrap());
+    assert!(!pin.is_set_low().unwrap());
+    destroy_pin(pin);
+}
+
+#[test]
+fn can_toggle() {
+    let mut pin = new_pin(&[
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0000_0011]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0000_0011]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b1000_0011]),
+    ]);
+    pin.toggle().unwrap();
+    destroy_pin(pin);
+}
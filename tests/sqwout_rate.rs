@@ -27,3 +27,29 @@ set!(set_1hz, Hz1, 0);
 set!(set_4khz, Khz4_096, 1);
 set!(set_8khz, Khz8_192, 2);
 set!(set_32khz, Khz32_768, 3);
+
+#[test]
+fn can_convert_to_and_from_hz() {
+    for (rate, hz) in [
+        (SqwOutRate::Hz1, 1),
+        (SqwOutRate::Khz4_096, 4096),
+        (SqwOutRate::Khz8_192, 8192),
+        (SqwOutRate::Khz32_768, 32768),
+    ] {
+        assert_eq!(hz, rate.hz());
+        assert_eq!(hz, u32::from(rate));
+        assert_eq!(Some(rate), SqwOutRate::from_hz(hz));
+    }
+    assert_eq!(None, SqwOutRate::from_hz(0));
+    assert_eq!(None, SqwOutRate::from_hz(32767));
+}
+
+#[cfg(feature = "fugit")]
+#[test]
+fn can_convert_to_and_from_fugit_rate() {
+    use fugit::RateExtU32;
+    assert_eq!(32768.Hz::<1, 1>(), SqwOutRate::Khz32_768.rate());
+    assert_eq!(1.Hz::<1, 1>(), fugit::HertzU32::from(SqwOutRate::Hz1));
+    assert_eq!(Some(SqwOutRate::Khz4_096), SqwOutRate::from_rate(4096.Hz()));
+    assert_eq!(None, SqwOutRate::from_rate(1.kHz()));
+}
@@ -0,0 +1,114 @@
+#![cfg(all(feature = "testing", feature = "chrono"))]
+
+use ds1307::testing::*;
+use ds1307::{DateTimeAccess, Ds1307, NaiveDate, Rtcc};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+mod common;
+use crate::common::{Register, ADDR};
+
+fn new(transactions: &[Transaction]) -> Ds1307<I2cMock> {
+    Ds1307::new(I2cMock::new(transactions))
+}
+
+fn destroy(rtc: Ds1307<I2cMock>) {
+    rtc.destroy().done();
+}
+
+fn datetime() -> ds1307::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2018, 8, 13)
+        .unwrap()
+        .and_hms_opt(23, 59, 58)
+        .unwrap()
+}
+
+#[test]
+fn datetime_read_matches_encoding() {
+    assert_eq!(
+        vec![I2cTrans::write_read(
+            ADDR,
+            vec![Register::SECONDS],
+            vec![0x58, 0x59, 0x23, 2, 0x13, 0x08, 0x18]
+        )],
+        expect_datetime_read(datetime())
+    );
+}
+
+#[test]
+fn can_expect_datetime_read() {
+    let mut rtc = new(&expect_datetime_read(datetime()));
+    assert_eq!(datetime(), rtc.datetime().unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_expect_set_datetime() {
+    for halted in [false, true] {
+        let mut rtc = new(&expect_set_datetime(datetime(), halted));
+        rtc.set_datetime(&datetime()).unwrap();
+        destroy(rtc);
+    }
+}
+
+#[test]
+fn can_expect_date_read() {
+    let mut rtc = new(&expect_date_read(datetime().date()));
+    assert_eq!(datetime().date(), rtc.date().unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_expect_set_date() {
+    let mut rtc = new(&expect_set_date(datetime().date()));
+    rtc.set_date(&datetime().date()).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn can_expect_time_read() {
+    let mut rtc = new(&expect_time_read(datetime().time()));
+    assert_eq!(datetime().time(), rtc.time().unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_expect_set_time() {
+    for halted in [false, true] {
+        let mut rtc = new(&expect_set_time(datetime().time(), halted));
+        rtc.set_time(&datetime().time()).unwrap();
+        destroy(rtc);
+    }
+}
+
+#[test]
+fn can_expect_running_state_changes() {
+    for running in [false, true] {
+        let transactions = [
+            expect_running_read(running),
+            expect_halt(running),
+            expect_set_running(running),
+        ]
+        .concat();
+        let mut rtc = new(&transactions);
+        assert_eq!(running, rtc.running().unwrap());
+        rtc.halt().unwrap();
+        rtc.set_running().unwrap();
+        destroy(rtc);
+    }
+}
+
+#[test]
+fn can_expect_ram_access() {
+    let transactions = [
+        expect_ram_write(2, &[0xAB, 0xCD]),
+        expect_ram_read(2, &[0xAB, 0xCD]),
+        expect_ram_write(0, &[]),
+    ]
+    .concat();
+    let mut rtc = new(&transactions);
+    rtc.write_ram(2, &[0xAB, 0xCD]).unwrap();
+    let mut data = [0; 2];
+    rtc.read_ram(2, &mut data).unwrap();
+    assert_eq!([0xAB, 0xCD], data);
+    rtc.write_ram(0, &[]).unwrap();
+    destroy(rtc);
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{format_datetime, Hours, NaiveDateTime, TimeInput, TimeInputError};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{datetime, destroy, new, Register, ADDR};
+
+fn time(hours: Hours, minutes: u8, seconds: u8) -> TimeInput {
+    TimeInput::Time {
+        hours,
+        minutes,
+        seconds,
+    }
+}
+
+#[test]
+fn can_parse_datetime() {
+    let expected = TimeInput::DateTime(datetime(2024, 2, 1, 13, 45, 0));
+    assert_eq!(Ok(expected), TimeInput::parse("2024-02-01 13:45:00"));
+    assert_eq!(Ok(expected), TimeInput::parse("2024-2-1T13:45"));
+    assert_eq!(Ok(expected), TimeInput::parse("  2024-02-01   13:45  "));
+}
+
+#[test]
+fn can_parse_time_of_day() {
+    assert_eq!(Ok(time(Hours::H24(13), 45, 0)), "13:45".parse());
+    assert_eq!(Ok(time(Hours::H24(0), 5, 30)), "0:05:30".parse());
+    assert_eq!(Ok(time(Hours::PM(1), 45, 0)), "1:45pm".parse());
+    assert_eq!(Ok(time(Hours::AM(12), 0, 15)), "12:00:15 AM".parse());
+}
+
+#[test]
+fn can_parse_adjustment() {
+    assert_eq!(Ok(TimeInput::Adjust(90)), "+90s".parse());
+    assert_eq!(Ok(TimeInput::Adjust(90)), "+90".parse());
+    assert_eq!(Ok(TimeInput::Adjust(-120)), "-2m".parse());
+    assert_eq!(Ok(TimeInput::Adjust(5400)), "+1h30m".parse());
+    assert_eq!(Ok(TimeInput::Adjust(5405)), "+1h30m5".parse());
+    assert_eq!(Ok(TimeInput::Adjust(-86400)), "-1D".parse());
+    assert_eq!(Ok(TimeInput::Adjust(i32::MIN)), "-2147483648s".parse());
+    assert_eq!(Ok(TimeInput::Adjust(i32::MAX)), "+2147483647".parse());
+}
+
+#[test]
+fn invalid_input_is_rejected() {
+    let syntax = Err(TimeInputError::Syntax);
+    let range = Err(TimeInputError::OutOfRange);
+    assert_eq!(syntax, TimeInput::parse(""));
+    assert_eq!(syntax, TimeInput::parse("now"));
+    assert_eq!(syntax, TimeInput::parse("13"));
+    assert_eq!(syntax, TimeInput::parse("13:45 xm"));
+    assert_eq!(syntax, TimeInput::parse("+"));
+    assert_eq!(syntax, TimeInput::parse("+90x"));
+    assert_eq!(syntax, TimeInput::parse("2024-02-01"));
+    assert_eq!(range, TimeInput::parse("24:00"));
+    assert_eq!(range, TimeInput::parse("12:60"));
+    assert_eq!(range, TimeInput::parse("13:00pm"));
+    assert_eq!(range, TimeInput::parse("0:30am"));
+    assert_eq!(range, TimeInput::parse("2024-02-30 00:00"));
+    assert_eq!(range, TimeInput::parse("+100000d"));
+    assert_eq!(range, TimeInput::parse("+99999999999"));
+    assert_eq!(range, TimeInput::parse("+2147483648s"));
+    assert_eq!(range, TimeInput::parse("-2147483649s"));
+}
+
+fn format(datetime: &NaiveDateTime, pattern: &str) -> String {
+    let mut out = String::new();
+    format_datetime(&mut out, datetime, pattern).unwrap();
+    out
+}
+
+#[test]
+fn can_format_datetime() {
+    let dt = datetime(2024, 2, 1, 13, 5, 9);
+    assert_eq!("2024-02-01 13:05:09", format(&dt, "%Y-%m-%d %H:%M:%S"));
+    assert_eq!("01/02/24 01:05 PM", format(&dt, "%d/%m/%y %I:%M %p"));
+    assert_eq!("Thu, 01 Feb 2024", format(&dt, "%a, %d %b %Y"));
+    assert_eq!("day 032, 100%", format(&dt, "day %j, 100%%"));
+    let midnight = datetime(2024, 12, 29, 0, 0, 0);
+    assert_eq!("12 AM Sun Dec", format(&midnight, "%I %p %a %b"));
+}
+
+#[test]
+fn unknown_specifier_is_error() {
+    let mut out = String::new();
+    let dt = datetime(2024, 2, 1, 13, 5, 9);
+    assert!(format_datetime(&mut out, &dt, "%Q").is_err());
+    assert!(format_datetime(&mut out, &dt, "%").is_err());
+}
+
+#[test]
+fn can_set_12h_time_input() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0x80]),
+        I2cTrans::write(ADDR, vec![Register::SECONDS, 0x80, 0x45, 0b0110_0001]),
+    ]);
+    rtc.set_time_input(&"1:45pm".parse().unwrap()).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn can_set_datetime_input() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0x00]),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x00, 0x45, 0x13, 5, 0x01, 0x02, 0x24],
+        ),
+    ]);
+    rtc.set_time_input(&"2024-02-01 13:45".parse().unwrap())
+        .unwrap();
+    destroy(rtc);
+}
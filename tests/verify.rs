@@ -0,0 +1,208 @@
+use ds1307::{ControlConfig, Error, VerifyFailures};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{destroy, new, Register, ADDR};
+
+macro_rules! assert_verify_failed {
+    ($result:expr) => {
+        match $result {
+            Err(Error::VerifyFailed) => (),
+            _ => panic!("VerifyFailed error not returned."),
+        }
+    };
+}
+
+#[test]
+fn disabled_by_default() {
+    let mut rtc = new(&trans_write!(RAM_BEGIN, [0xAB]));
+    assert!(!rtc.write_verification());
+    rtc.write_ram(0, &[0xAB]).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn can_verify_ram_write() {
+    let mut rtc = new(&[
+        I2cTrans::write(ADDR, vec![Register::RAM_BEGIN + 1, 0xAB, 0xCD]),
+        I2cTrans::write_read(ADDR, vec![Register::RAM_BEGIN + 1], vec![0xAB, 0xCD]),
+    ]);
+    rtc.set_write_verification(true);
+    rtc.write_ram(1, &[0xAB, 0xCD]).unwrap();
+    assert_eq!(VerifyFailures::default(), rtc.verification_failures());
+    destroy(rtc);
+}
+
+#[test]
+fn ram_mismatch_is_reported() {
+    let mut rtc = new(&[
+        I2cTrans::write(ADDR, vec![Register::RAM_BEGIN, 0xAB]),
+        I2cTrans::write_read(ADDR, vec![Register::RAM_BEGIN], vec![0xAF]),
+    ]);
+    rtc.set_write_verification(true);
+    assert_verify_failed!(rtc.write_ram(0, &[0xAB]));
+    assert_eq!(1, rtc.verification_failures().ram);
+    assert_eq!(1, rtc.verification_failures().total());
+    destroy(rtc);
+}
+
+#[test]
+fn control_mismatch_is_reported() {
+    let mut rtc = new(&[
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0000_0011]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0000_0001]),
+    ]);
+    rtc.set_write_verification(true);
+    assert_verify_failed!(rtc.set_control(ControlConfig::default()));
+    assert_eq!(1, rtc.verification_failures().control);
+    rtc.reset_verification_failures();
+    assert_eq!(0, rtc.verification_failures().total());
+    destroy(rtc);
+}
+
+#[test]
+fn read_modify_write_is_verified() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0]),
+        I2cTrans::write(ADDR, vec![Register::SQWOUT, 0b0001_0000]),
+        I2cTrans::write_read(ADDR, vec![Register::SQWOUT], vec![0b0001_0000]),
+    ]);
+    rtc.set_write_verification(true);
+    rtc.enable_square_wave_output().unwrap();
+    destroy(rtc);
+}
+
+#[cfg(feature = "chrono")]
+mod timekeeping {
+    use super::*;
+    use ds1307::{DateTimeAccess, Hours, NaiveDate, NaiveDateTime, Rtcc, Timelike};
+
+    fn datetime() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2018, 8, 31)
+            .unwrap()
+            .and_hms_opt(23, 59, 58)
+            .unwrap()
+    }
+
+    fn set_datetime(read_back: Vec<u8>) -> Vec<I2cTrans> {
+        vec![
+            I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+            I2cTrans::write(
+                ADDR,
+                vec![Register::SECONDS, 0x58, 0x59, 0x23, 6, 0x31, 0x08, 0x18],
+            ),
+            I2cTrans::write_read(ADDR, vec![Register::SECONDS], read_back),
+        ]
+    }
+
+    #[test]
+    fn exact_datetime_is_accepted() {
+        let mut rtc = new(&set_datetime(vec![0x58, 0x59, 0x23, 6, 0x31, 0x08, 0x18]));
+        rtc.set_write_verification(true);
+        rtc.set_datetime(&datetime()).unwrap();
+        destroy(rtc);
+    }
+
+    #[test]
+    fn ticked_datetime_is_accepted() {
+        let mut rtc = new(&set_datetime(vec![0x00, 0x00, 0x00, 7, 0x01, 0x09, 0x18]));
+        rtc.set_write_verification(true);
+        rtc.set_datetime(&datetime()).unwrap();
+        destroy(rtc);
+    }
+
+    #[test]
+    fn datetime_mismatch_is_reported() {
+        let mut rtc = new(&set_datetime(vec![0x01, 0x00, 0x00, 7, 0x01, 0x09, 0x18]));
+        rtc.set_write_verification(true);
+        assert_verify_failed!(rtc.set_datetime(&datetime()));
+        assert_eq!(1, rtc.verification_failures().timekeeping);
+        destroy(rtc);
+    }
+
+    #[test]
+    fn ticked_time_is_accepted() {
+        let mut rtc = new(&[
+            I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+            I2cTrans::write_read(
+                ADDR,
+                vec![Register::SECONDS],
+                vec![0x10, 0x00, 0x12, 6, 0x31, 0x08, 0x18],
+            ),
+            I2cTrans::write(ADDR, vec![Register::SECONDS, 0x59, 0x59, 0x23]),
+            I2cTrans::write_read(
+                ADDR,
+                vec![Register::SECONDS],
+                vec![0x00, 0x00, 0x00, 7, 0x01, 0x09, 0x18],
+            ),
+        ]);
+        rtc.set_write_verification(true);
+        rtc.set_time(&datetime().time().with_second(59).unwrap())
+            .unwrap();
+        destroy(rtc);
+    }
+
+    #[test]
+    fn date_mismatch_is_reported() {
+        let mut rtc = new(&[
+            I2cTrans::write_read(
+                ADDR,
+                vec![Register::SECONDS],
+                vec![0x10, 0x00, 0x12, 5, 0x30, 0x08, 0x18],
+            ),
+            I2cTrans::write(ADDR, vec![Register::DOW, 6, 0x31, 0x08, 0x18]),
+            I2cTrans::write_read(
+                ADDR,
+                vec![Register::SECONDS],
+                vec![0x10, 0x00, 0x12, 6, 0x31, 0x08, 0x19],
+            ),
+        ]);
+        rtc.set_write_verification(true);
+        assert_verify_failed!(rtc.set_date(&datetime().date()));
+        assert_eq!(1, rtc.verification_failures().timekeeping);
+        destroy(rtc);
+    }
+
+    fn write_with_carry(before: [u8; 7], write: Vec<u8>, after: [u8; 7]) -> Vec<I2cTrans> {
+        vec![
+            I2cTrans::write_read(ADDR, vec![Register::SECONDS], before.to_vec()),
+            I2cTrans::write(ADDR, write),
+            I2cTrans::write_read(ADDR, vec![Register::SECONDS], after.to_vec()),
+        ]
+    }
+
+    #[test]
+    fn carry_into_written_minutes_is_accepted() {
+        let mut rtc = new(&write_with_carry(
+            [0x59, 0x10, 0x12, 1, 0x13, 0x08, 0x18],
+            vec![Register::MINUTES, 0x30],
+            [0x00, 0x31, 0x12, 1, 0x13, 0x08, 0x18],
+        ));
+        rtc.set_write_verification(true);
+        rtc.set_minutes(30).unwrap();
+        destroy(rtc);
+    }
+
+    #[test]
+    fn carry_into_written_hours_is_accepted() {
+        let mut rtc = new(&write_with_carry(
+            [0x59, 0x59, 0x12, 1, 0x13, 0x08, 0x18],
+            vec![Register::HOURS, 0x05],
+            [0x00, 0x00, 0x06, 1, 0x13, 0x08, 0x18],
+        ));
+        rtc.set_write_verification(true);
+        rtc.set_hours(Hours::H24(5)).unwrap();
+        destroy(rtc);
+    }
+
+    #[test]
+    fn minutes_without_carry_mismatch_is_reported() {
+        let mut rtc = new(&write_with_carry(
+            [0x10, 0x10, 0x12, 1, 0x13, 0x08, 0x18],
+            vec![Register::MINUTES, 0x30],
+            [0x11, 0x31, 0x12, 1, 0x13, 0x08, 0x18],
+        ));
+        rtc.set_write_verification(true);
+        assert_verify_failed!(rtc.set_minutes(30));
+        destroy(rtc);
+    }
+}
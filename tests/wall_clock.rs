@@ -0,0 +1,119 @@
+#![cfg(feature = "embassy-time")]
+// embassy-time requires a much newer compiler than the crate MSRV anyway.
+#![allow(clippy::incompatible_msrv)]
+
+use ds1307::WallClock;
+use embassy_time::{Duration, Instant, MockDriver};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use std::sync::{Mutex, MutexGuard};
+mod common;
+use crate::common::{datetime, new, read_datetime, Register, ADDR};
+
+// Required by the timer queue of the mock time driver. No executor is used here.
+#[no_mangle]
+fn __pender(_context: *mut ()) {}
+
+// The mock time driver is global so the tests must not run in parallel.
+static DRIVER_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_driver() -> MutexGuard<'static, ()> {
+    let guard = DRIVER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    MockDriver::get().reset();
+    guard
+}
+
+#[test]
+fn reads_rtc_only_on_creation_within_interval() {
+    let _lock = lock_driver();
+    let rtc = new(&[read_datetime(&datetime(2018, 8, 13, 23, 59, 10))]);
+    let mut clock = WallClock::new(rtc, Duration::from_secs(60)).unwrap();
+    assert_eq!(datetime(2018, 8, 13, 23, 59, 10), clock.now().unwrap());
+    MockDriver::get().advance(Duration::from_millis(30_500));
+    assert_eq!(
+        datetime(2018, 8, 13, 23, 59, 40) + chrono::Duration::milliseconds(500),
+        clock.now().unwrap()
+    );
+    clock.destroy().destroy().done();
+}
+
+#[test]
+fn resyncs_after_interval() {
+    let _lock = lock_driver();
+    let rtc = new(&[
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 10)),
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 21)),
+    ]);
+    let mut clock = WallClock::new(rtc, Duration::from_secs(10)).unwrap();
+    MockDriver::get().advance(Duration::from_secs(10));
+    assert_eq!(datetime(2018, 8, 13, 23, 59, 21), clock.now().unwrap());
+    MockDriver::get().advance(Duration::from_secs(2));
+    assert_eq!(datetime(2018, 8, 13, 23, 59, 23), clock.now().unwrap());
+    clock.destroy().destroy().done();
+}
+
+#[test]
+fn resync_keeps_fraction_of_second() {
+    let _lock = lock_driver();
+    let rtc = new(&[
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 10)),
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 20)),
+    ]);
+    let mut clock = WallClock::new(rtc, Duration::from_secs(10)).unwrap();
+    MockDriver::get().advance(Duration::from_millis(9_800));
+    let before = clock.now().unwrap();
+    MockDriver::get().advance(Duration::from_millis(700));
+    let after = clock.now().unwrap();
+    assert!(after >= before);
+    assert_eq!(
+        datetime(2018, 8, 13, 23, 59, 20) + chrono::Duration::milliseconds(500),
+        after
+    );
+    clock.destroy().destroy().done();
+}
+
+#[test]
+fn can_set_datetime() {
+    let _lock = lock_driver();
+    let rtc = new(&[
+        read_datetime(&datetime(2018, 8, 13, 23, 59, 10)),
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+        I2cTrans::write(
+            ADDR,
+            vec![Register::SECONDS, 0x05, 0x30, 0x12, 0x02, 0x13, 0x08, 0x18],
+        ),
+    ]);
+    let mut clock = WallClock::new(rtc, Duration::from_secs(60)).unwrap();
+    MockDriver::get().advance(Duration::from_secs(5));
+    clock
+        .set_datetime(&datetime(2018, 8, 13, 12, 30, 5))
+        .unwrap();
+    MockDriver::get().advance(Duration::from_secs(5));
+    assert_eq!(datetime(2018, 8, 13, 12, 30, 10), clock.now().unwrap());
+    clock.destroy().destroy().done();
+}
+
+#[test]
+fn can_convert_between_instant_and_datetime() {
+    let _lock = lock_driver();
+    MockDriver::get().advance(Duration::from_secs(100));
+    let rtc = new(&[read_datetime(&datetime(2018, 8, 13, 23, 59, 10))]);
+    let clock = WallClock::new(rtc, Duration::from_secs(60)).unwrap();
+    assert_eq!(
+        datetime(2018, 8, 13, 23, 59, 30),
+        clock.to_datetime(Instant::from_secs(120))
+    );
+    assert_eq!(
+        datetime(2018, 8, 13, 23, 58, 0),
+        clock.to_datetime(Instant::from_secs(30))
+    );
+    assert_eq!(
+        Some(Instant::from_secs(190)),
+        clock.to_instant(&(datetime(2018, 8, 13, 23, 59, 59) + chrono::Duration::seconds(41)))
+    );
+    assert_eq!(
+        Some(Instant::from_secs(10)),
+        clock.to_instant(&datetime(2018, 8, 13, 23, 57, 40))
+    );
+    assert_eq!(None, clock.to_instant(&datetime(2018, 8, 13, 23, 0, 0)));
+    clock.destroy().destroy().done();
+}
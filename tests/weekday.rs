@@ -0,0 +1,85 @@
+#![cfg(feature = "chrono")]
+
+use ds1307::{DateTimeAccess, Error, NaiveDate, Rtcc, WeekdayConvention};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+mod common;
+use crate::common::{destroy, new, Register, ADDR};
+
+// 2018-08-12 is a Sunday
+const SUNDAY: [u8; 3] = [0x12, 0x08, 0x18];
+
+#[test]
+fn default_convention_is_sunday_one() {
+    let rtc = new(&[]);
+    assert_eq!(WeekdayConvention::SundayOne, rtc.weekday_convention());
+    destroy(rtc);
+}
+
+macro_rules! consistent_test {
+    ($name:ident, $convention:ident, $dow:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let mut rtc = new(&trans_read!(DOW, [$dow, SUNDAY[0], SUNDAY[1], SUNDAY[2]]));
+            rtc.set_weekday_convention(WeekdayConvention::$convention);
+            assert_eq!($expected, rtc.weekday_consistent().unwrap());
+            destroy(rtc);
+        }
+    };
+}
+consistent_test!(sunday_one_consistent, SundayOne, 1, true);
+consistent_test!(sunday_one_inconsistent, SundayOne, 7, false);
+consistent_test!(monday_one_consistent, MondayOne, 7, true);
+consistent_test!(monday_one_inconsistent, MondayOne, 1, false);
+
+#[test]
+fn consistency_of_invalid_date_returns_error() {
+    let mut rtc = new(&trans_read!(DOW, [1, 0x31, 0x02, 0x18]));
+    assert_invalid_input_data!(rtc.weekday_consistent());
+    destroy(rtc);
+}
+
+#[test]
+fn repair_consistent_weekday_does_nothing() {
+    let mut rtc = new(&trans_read!(DOW, [1, SUNDAY[0], SUNDAY[1], SUNDAY[2]]));
+    assert!(!rtc.repair_weekday().unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn can_repair_weekday() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(
+            ADDR,
+            vec![Register::DOW],
+            vec![3, SUNDAY[0], SUNDAY[1], SUNDAY[2]],
+        ),
+        I2cTrans::write(ADDR, vec![Register::DOW, 7]),
+    ]);
+    rtc.set_weekday_convention(WeekdayConvention::MondayOne);
+    assert!(rtc.repair_weekday().unwrap());
+    destroy(rtc);
+}
+
+#[test]
+fn set_datetime_honours_convention() {
+    let mut rtc = new(&[
+        I2cTrans::write_read(ADDR, vec![Register::SECONDS], vec![0]),
+        I2cTrans::write(ADDR, vec![Register::SECONDS, 0, 0, 0, 7, 0x12, 0x08, 0x18]),
+    ]);
+    rtc.set_weekday_convention(WeekdayConvention::MondayOne);
+    let datetime = NaiveDate::from_ymd_opt(2018, 8, 12)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    rtc.set_datetime(&datetime).unwrap();
+    destroy(rtc);
+}
+
+#[test]
+fn set_date_honours_convention() {
+    let mut rtc = new(&trans_write!(DOW, [1, 0x13, 0x08, 0x18]));
+    rtc.set_weekday_convention(WeekdayConvention::MondayOne);
+    rtc.set_date(&NaiveDate::from_ymd_opt(2018, 8, 13).unwrap())
+        .unwrap();
+    destroy(rtc);
+}